@@ -1,23 +1,34 @@
 use crate::client::api::internal::InternalApi;
 use crate::error::Error;
+use crate::{CallOptions, Diagnostics};
 use crate::messages::{MessageRequest, MessageResponse};
-use crate::states::{DefaultAudioNodesState, GlobalObjectState, SettingsState};
+use crate::states::{DefaultAudioNodesState, SettingsState};
 use std::sync::Arc;
 
 pub struct CoreApi {
     pub(crate) api: Arc<InternalApi>,
+    pub(crate) options: CallOptions,
 }
 
 impl CoreApi {
     pub(crate) fn new(api: Arc<InternalApi>) -> Self {
         CoreApi {
             api,
+            options: CallOptions::default(),
+        }
+    }
+
+    /// Return a copy of this API whose calls use given options instead of client ones
+    pub fn with_options(&self, options: CallOptions) -> Self {
+        CoreApi {
+            api: self.api.clone(),
+            options,
         }
     }
 
     pub(crate) fn check_session_manager_registered(&self) -> Result<(), Error> {
         let request = MessageRequest::CheckSessionManagerRegistered;
-        let response = self.api.send_request(&request);
+        let response = self.api.send_request(&request, &self.options);
         match response {
             Ok(MessageResponse::CheckSessionManagerRegistered{
                    session_manager_registered,
@@ -42,7 +53,7 @@ impl CoreApi {
 
     pub fn get_settings(&self) -> Result<SettingsState, Error> {
         let request = MessageRequest::Settings;
-        let response = self.api.send_request(&request);
+        let response = self.api.send_request(&request, &self.options);
         match response {
             Ok(MessageResponse::Settings(value)) => Ok(value),
             Err(value) => Err(value),
@@ -52,11 +63,11 @@ impl CoreApi {
         }
     }
 
-    pub(crate) fn get_settings_state(&self) -> Result<GlobalObjectState, Error> {
-        let request = MessageRequest::SettingsState;
-        let response = self.api.send_request(&request);
+    pub fn get_default_audio_nodes(&self) -> Result<DefaultAudioNodesState, Error> {
+        let request = MessageRequest::DefaultAudioNodes;
+        let response = self.api.send_request(&request, &self.options);
         match response {
-            Ok(MessageResponse::SettingsState(value)) => Ok(value),
+            Ok(MessageResponse::DefaultAudioNodes(value)) => Ok(value),
             Err(value) => Err(value),
             Ok(value) => Err(Error {
                 description: format!("Received unexpected response: {:?}", value),
@@ -64,11 +75,14 @@ impl CoreApi {
         }
     }
 
-    pub fn get_default_audio_nodes(&self) -> Result<DefaultAudioNodesState, Error> {
-        let request = MessageRequest::DefaultAudioNodes;
-        let response = self.api.send_request(&request);
+    pub fn diagnostics(&self) -> Result<Diagnostics, Error> {
+        let request = MessageRequest::Diagnostics;
+        let response = self.api.send_request(&request, &self.options);
         match response {
-            Ok(MessageResponse::DefaultAudioNodes(value)) => Ok(value),
+            Ok(MessageResponse::Diagnostics(value)) => Ok(Diagnostics {
+                pending_requests: self.api.pending_requests_count(),
+                ..value
+            }),
             Err(value) => Err(value),
             Ok(value) => Err(Error {
                 description: format!("Received unexpected response: {:?}", value),
@@ -76,11 +90,12 @@ impl CoreApi {
         }
     }
 
-    pub(crate) fn get_default_audio_nodes_state(&self) -> Result<GlobalObjectState, Error> {
-        let request = MessageRequest::DefaultAudioNodesState;
-        let response = self.api.send_request(&request);
+    /// Wait until settings, default audio nodes and all nodes are initialized
+    pub(crate) fn wait_ready(&self) -> Result<(), Error> {
+        let request = MessageRequest::WaitReady;
+        let response = self.api.send_request(&request, &self.options);
         match response {
-            Ok(MessageResponse::DefaultAudioNodesState(value)) => Ok(value),
+            Ok(MessageResponse::Ready) => Ok(()),
             Err(value) => Err(value),
             Ok(value) => Err(Error {
                 description: format!("Received unexpected response: {:?}", value),
@@ -1,4 +1,5 @@
 use crate::test_utils::fixtures::{isolated_client, shared_client, PipewireTestClient};
+use crate::{CallOptions, CancellationToken};
 use rstest::rstest;
 use serial_test::serial;
 
@@ -25,4 +26,30 @@ pub fn default_audio_nodes(#[from(shared_client)] client: PipewireTestClient) {
     let default_audio_nodes = client.core().get_default_audio_nodes().unwrap();
     assert_eq!(false, default_audio_nodes.sink.is_empty());
     assert_eq!(false, default_audio_nodes.source.is_empty());
-}
\ No newline at end of file
+}
+
+#[rstest]
+#[serial]
+pub fn cancelled_call(#[from(shared_client)] client: PipewireTestClient) {
+    let cancellation_token = CancellationToken::new();
+    let options = CallOptions::new().with_cancellation_token(cancellation_token.clone());
+    let core = client.core().with_options(options);
+    core.get_settings().unwrap();
+    cancellation_token.cancel();
+    let error = core.get_settings().unwrap_err();
+    assert_eq!("Cancelled", error.description);
+    // Client is still usable after a cancelled call
+    client.core().get_settings().unwrap();
+}
+
+#[rstest]
+#[serial]
+pub fn diagnostics(#[from(shared_client)] client: PipewireTestClient) {
+    let diagnostics = client.core().diagnostics().unwrap();
+    assert_eq!(true, diagnostics.nodes > 0);
+    assert_eq!(true, diagnostics.proxies >= diagnostics.nodes + diagnostics.streams + diagnostics.orphans);
+    // Info listener is kept during node lifetime, released ones are pruned
+    assert_eq!(true, diagnostics.listeners >= diagnostics.nodes);
+    assert_eq!(0, diagnostics.waiters);
+    assert_eq!(0, diagnostics.pending_requests);
+}
@@ -1,7 +1,8 @@
 use crate::client::channel::ClientChannel;
 use crate::error::Error;
 use crate::messages::{MessageRequest, MessageResponse};
-use std::time::Duration;
+use crate::CallOptions;
+use std::time::{Duration, Instant};
 
 pub(crate) struct InternalApi {
     pub(crate) channel: ClientChannel<MessageRequest, MessageResponse>,
@@ -19,12 +20,42 @@ impl InternalApi {
         }
     }
 
+    pub(crate) fn pending_requests_count(&self) -> usize {
+        self.channel.pending_requests_count()
+    }
+
     pub(crate) fn wait_response_with_timeout(&self, timeout: Duration) -> Result<MessageResponse, Error> {
         self.channel.receive_timeout(timeout)
     }
 
-    pub(crate) fn send_request(&self, request: &MessageRequest) -> Result<MessageResponse, Error> {
-        let response = self.channel.send(request.clone());
+    /// Errors fired outside of a request context, other global messages are only relevant
+    /// during initialization
+    pub(crate) fn global_errors(&self) -> Vec<Error> {
+        self.channel.receive_all()
+            .into_iter()
+            .filter_map(|message| match message {
+                MessageResponse::Error(value) => Some(value),
+                _ => None
+            })
+            .collect()
+    }
+
+    /// Options timeout fallback to client one
+    pub(crate) fn timeout(&self, options: &CallOptions) -> Duration {
+        options.timeout.unwrap_or(self.timeout)
+    }
+
+    /// Deadline shared by every sub-request of a call
+    pub(crate) fn deadline(&self, options: &CallOptions) -> Instant {
+        Instant::now() + self.timeout(options)
+    }
+
+    pub(crate) fn send_request(&self, request: &MessageRequest, options: &CallOptions) -> Result<MessageResponse, Error> {
+        let response = self.channel.send_cancellable(
+            request.clone(),
+            self.timeout(options),
+            options.cancellation_token.as_ref()
+        );
         match response {
             Ok(value) => {
                 match value {
@@ -1,56 +1,43 @@
 use crate::client::api::internal::InternalApi;
 use crate::error::Error;
+use crate::CallOptions;
 use crate::messages::{MessageRequest, MessageResponse};
-use crate::states::{GlobalId, GlobalObjectState};
-use crate::utils::Backoff;
-use crate::{Direction, NodeInfo};
+use crate::states::GlobalId;
+use crate::{Direction, NodeCreateOptions, NodeInfo, NodeStatus};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 pub struct NodeApi {
-    api: Arc<InternalApi>
+    api: Arc<InternalApi>,
+    options: CallOptions,
 }
 
 impl NodeApi {
     pub(crate) fn new(api: Arc<InternalApi>) -> Self {
         NodeApi {
             api,
+            options: CallOptions::default(),
         }
     }
 
-    pub(crate) fn state(
-        &self,
-        id: &GlobalId,
-    ) -> Result<GlobalObjectState, Error> {
-        let request = MessageRequest::NodeState(id.clone());
-        let response = self.api.send_request(&request);
-        match response {
-            Ok(MessageResponse::NodeState(value)) => Ok(value),
-            Err(value) => Err(value),
-            Ok(value) => Err(Error {
-                description: format!("Received unexpected response: {:?}", value),
-            }),
+    pub fn with_options(&self, options: CallOptions) -> Self {
+        NodeApi {
+            api: self.api.clone(),
+            options,
         }
     }
 
-    pub(crate) fn states(
-        &self,
-    ) -> Result<Vec<GlobalObjectState>, Error> {
-        let request = MessageRequest::NodeStates;
-        let response = self.api.send_request(&request);
-        match response {
-            Ok(MessageResponse::NodeStates(value)) => Ok(value),
-            Err(value) => Err(value),
-            Ok(value) => Err(Error {
-                description: format!("Received unexpected response: {:?}", value),
-            }),
-        }
+    /// Sub-requests of a call share its deadline, each one gets the time left
+    fn until(&self, deadline: Instant) -> Self {
+        self.with_options(self.options.until(deadline))
     }
 
     pub(crate) fn count(
         &self,
     ) -> Result<u32, Error> {
         let request = MessageRequest::NodeCount;
-        let response = self.api.send_request(&request);
+        let response = self.api.send_request(&request, &self.options);
         match response {
             Ok(MessageResponse::NodeCount(value)) => Ok(value),
             Err(value) => Err(value),
@@ -69,7 +56,7 @@ impl NodeApi {
             name,
             direction,
         };
-        let response = self.api.send_request(&request);
+        let response = self.api.send_request(&request, &self.options);
         match response {
             Ok(MessageResponse::GetNode(value)) => Ok(value),
             Err(value) => Err(value),
@@ -79,36 +66,57 @@ impl NodeApi {
         }
     }
 
+    pub fn get_by_id(&self, id: u32) -> Result<NodeInfo, Error> {
+        let request = MessageRequest::GetNodeById(GlobalId::from(id));
+        let response = self.api.send_request(&request, &self.options);
+        match response {
+            Ok(MessageResponse::GetNode(value)) => Ok(value),
+            Err(value) => Err(value),
+            Ok(value) => Err(Error {
+                description: format!("Received unexpected response: {:?}", value),
+            }),
+        }
+    }
+
+    pub fn get_by_serial(&self, serial: u64) -> Result<NodeInfo, Error> {
+        let request = MessageRequest::GetNodeBySerial(serial);
+        let response = self.api.send_request(&request, &self.options);
+        match response {
+            Ok(MessageResponse::GetNode(value)) => Ok(value),
+            Err(value) => Err(value),
+            Ok(value) => Err(Error {
+                description: format!("Received unexpected response: {:?}", value),
+            }),
+        }
+    }
+
+    /// Return all nodes whose properties match predicate (e.g. alsa.card_name)
+    pub fn find<F>(&self, predicate: F) -> Result<Vec<NodeInfo>, Error>
+    where
+        F: Fn(&HashMap<String, String>) -> bool + Send + Sync + 'static,
+    {
+        let request = MessageRequest::FindNodes(predicate.into());
+        let response = self.api.send_request(&request, &self.options);
+        match response {
+            Ok(MessageResponse::FindNodes(value)) => Ok(value),
+            Err(value) => Err(value),
+            Ok(value) => Err(Error {
+                description: format!("Received unexpected response: {:?}", value),
+            }),
+        }
+    }
+
     pub fn create(
         &self,
-        name: String,
-        description: String,
-        nickname: String,
-        direction: Direction,
-        channels: u16,
-    ) -> Result<(), Error> {
-        let request = MessageRequest::CreateNode {
-            name,
-            description,
-            nickname,
-            direction,
-            channels,
-        };
-        let response = self.api.send_request(&request);
+        options: NodeCreateOptions,
+    ) -> Result<NodeInfo, Error> {
+        let deadline = self.api.deadline(&self.options);
+        let request = MessageRequest::CreateNode(options);
+        let response = self.api.send_request(&request, &self.options.until(deadline));
         match response {
             Ok(MessageResponse::CreateNode(id)) => {
-                let operation = move || {
-                    let state = self.state(&id)?;
-                    return if state == GlobalObjectState::Initialized {
-                        Ok(())
-                    } else {
-                        Err(Error {
-                            description: "Created node not yet initialized".to_string(),
-                        })
-                    }
-                };
-                let mut backoff = Backoff::constant(self.api.timeout.as_millis());
-                backoff.retry(operation)
+                self.until(deadline).wait_initialized(id.clone())?;
+                self.until(deadline).get_by_id(id.into())
             },
             Ok(MessageResponse::Error(value)) => Err(value),
             Err(value) => Err(value),
@@ -118,9 +126,21 @@ impl NodeApi {
         }
     }
 
+    fn wait_initialized(&self, id: GlobalId) -> Result<(), Error> {
+        let request = MessageRequest::WaitNodeInitialized(id);
+        let response = self.api.send_request(&request, &self.options);
+        match response {
+            Ok(MessageResponse::NodeInitialized(_)) => Ok(()),
+            Err(value) => Err(value),
+            Ok(value) => Err(Error {
+                description: format!("Received unexpected response: {:?}", value),
+            }),
+        }
+    }
+
     pub fn delete(&self, id: u32) -> Result<(), Error> {
         let request = MessageRequest::DeleteNode(GlobalId::from(id));
-        let response = self.api.send_request(&request);
+        let response = self.api.send_request(&request, &self.options);
         match response {
             Ok(MessageResponse::DeleteNode) => Ok(()),
             Err(value) => Err(value),
@@ -130,12 +150,61 @@ impl NodeApi {
         }
     }
 
+    /// Returned state is the one reported by server once command has been processed
+    pub fn suspend(&self, id: u32) -> Result<NodeStatus, Error> {
+        let deadline = self.api.deadline(&self.options);
+        let request = MessageRequest::SuspendNode(GlobalId::from(id));
+        let response = self.api.send_request(&request, &self.options.until(deadline));
+        match response {
+            Ok(MessageResponse::SuspendNode) => self.until(deadline).get_by_id(id).map(|node| node.state),
+            Err(value) => Err(value),
+            Ok(value) => Err(Error {
+                description: format!("Received unexpected response: {:?}", value),
+            }),
+        }
+    }
+
+    /// Returned state is the one reported by server once command has been processed
+    pub fn resume(&self, id: u32) -> Result<NodeStatus, Error> {
+        let deadline = self.api.deadline(&self.options);
+        let request = MessageRequest::ResumeNode(GlobalId::from(id));
+        let response = self.api.send_request(&request, &self.options.until(deadline));
+        match response {
+            Ok(MessageResponse::ResumeNode) => self.until(deadline).get_by_id(id).map(|node| node.state),
+            Err(value) => Err(value),
+            Ok(value) => Err(Error {
+                description: format!("Received unexpected response: {:?}", value),
+            }),
+        }
+    }
+
+    pub fn update_properties(
+        &self,
+        id: u32,
+        properties: HashMap<String, String>,
+    ) -> Result<NodeInfo, Error> {
+        let request = MessageRequest::UpdateNodeProperties {
+            id: GlobalId::from(id),
+            properties,
+        };
+        let deadline = self.api.deadline(&self.options);
+        let response = self.api.send_request(&request, &self.options.until(deadline));
+        match response {
+            // Answered once server reported updated properties
+            Ok(MessageResponse::UpdateNodeProperties) => self.until(deadline).get_by_id(id),
+            Err(value) => Err(value),
+            Ok(value) => Err(Error {
+                description: format!("Received unexpected response: {:?}", value),
+            }),
+        }
+    }
+
     pub fn enumerate(
         &self,
         direction: Direction,
     ) -> Result<Vec<NodeInfo>, Error> {
         let request = MessageRequest::EnumerateNodes(direction);
-        let response = self.api.send_request(&request);
+        let response = self.api.send_request(&request, &self.options);
         match response {
             Ok(MessageResponse::EnumerateNodes(value)) => Ok(value),
             Err(value) => Err(value),
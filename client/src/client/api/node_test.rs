@@ -1,11 +1,25 @@
 use crate::states::NodeState;
-use crate::test_utils::fixtures::{shared_client, PipewireTestClient};
-use crate::Direction;
+use crate::test_utils::fixtures::{client2, shared_client, PipewireTestClient};
+use crate::{CallOptions, Direction, NodeCreateOptions, NodeStatus, PipewireClient};
+use pipewire_spa_utils::audio::{AudioChannel, AudioChannelLayout, AudioChannelPosition, AudioSampleFormat};
 use rstest::rstest;
 use serial_test::serial;
 use std::any::TypeId;
+use std::collections::HashMap;
+use std::thread;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+fn create_options(node_name: &String, direction: Direction) -> NodeCreateOptions {
+    NodeCreateOptions::new(
+        node_name.clone(),
+        node_name.clone(),
+        node_name.clone(),
+        direction,
+        AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FR])
+    )
+}
+
 fn internal_enumerate(client: &PipewireTestClient, direction: Direction) -> Vec<String> {
     let nodes = client.node().enumerate(direction).unwrap();
     assert_eq!(false, nodes.is_empty());
@@ -15,8 +29,9 @@ fn internal_enumerate(client: &PipewireTestClient, direction: Direction) -> Vec<
     assert_eq!(true, default_node.is_some());
     let listeners = client.core().get_listeners().unwrap();
     let node_listeners = listeners.get(&TypeId::of::<NodeState>()).unwrap();
+    // Info listener is kept during node lifetime
     for (_, listeners) in node_listeners {
-        assert_eq!(0, listeners.len());
+        assert_eq!(vec!["info".to_string()], *listeners);
     }
     nodes.iter()
         .map(move |node| node.name.clone())
@@ -26,17 +41,12 @@ fn internal_enumerate(client: &PipewireTestClient, direction: Direction) -> Vec<
 fn internal_create(client: &PipewireTestClient, direction: Direction) -> String {
     let node_name = Uuid::new_v4().to_string();
     client.node()
-        .create(
-            node_name.clone(),
-            node_name.clone(),
-            node_name.clone(),
-            direction,
-            2
-        ).unwrap();
+        .create(create_options(&node_name, direction)).unwrap();
     let listeners = client.core().get_listeners().unwrap();
     let node_listeners = listeners.get(&TypeId::of::<NodeState>()).unwrap();
+    // Info listener is kept during node lifetime
     for (_, listeners) in node_listeners {
-        assert_eq!(0, listeners.len());
+        assert_eq!(vec!["info".to_string()], *listeners);
     }
     node_name
 }
@@ -80,21 +90,9 @@ fn create_twice_same_direction(
 ) {
     let node_name = Uuid::new_v4().to_string();
     client.node()
-        .create(
-            node_name.clone(),
-            node_name.clone(),
-            node_name.clone(),
-            Direction::Output,
-            2
-        ).unwrap();
+        .create(create_options(&node_name, Direction::Output)).unwrap();
     let error = client.node()
-        .create(
-            node_name.clone(),
-            node_name.clone(),
-            node_name.clone(),
-            Direction::Output,
-            2
-        ).unwrap_err();
+        .create(create_options(&node_name, Direction::Output)).unwrap_err();
     assert_eq!(
         format!("Node with name({}) already exists", node_name),
         error.description
@@ -108,21 +106,9 @@ fn create_twice_different_direction(
 ) {
     let node_name = Uuid::new_v4().to_string();
     client.node()
-        .create(
-            node_name.clone(),
-            node_name.clone(),
-            node_name.clone(),
-            Direction::Input,
-            2
-        ).unwrap();
+        .create(create_options(&node_name, Direction::Input)).unwrap();
     client.node()
-        .create(
-            node_name.clone(),
-            node_name.clone(),
-            node_name.clone(),
-            Direction::Output,
-            2
-        ).unwrap();
+        .create(create_options(&node_name, Direction::Output)).unwrap();
 }
 
 #[rstest]
@@ -145,4 +131,363 @@ fn create_then_enumerate_output(
     let node = internal_create(&client, direction.clone());
     let nodes = internal_enumerate(&client, direction.clone());
     assert_eq!(true, nodes.contains(&node))
-}
\ No newline at end of file
+}
+
+#[rstest]
+#[serial]
+fn create_returns_node_info(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node_name = Uuid::new_v4().to_string();
+    let node = client.node()
+        .create(create_options(&node_name, Direction::Output))
+        .unwrap();
+    assert_eq!(node_name, node.name);
+    assert_eq!(Some(Direction::Output), node.direction);
+    assert_eq!(2, node.format.unwrap().channels.default());
+}
+
+#[rstest]
+#[serial]
+fn create_many_returns_matching_ids(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    // Creations are concurrent, each one must get the id of its own node
+    let handles = (0..5)
+        .map(|_| {
+            let client = PipewireClient::clone(&client);
+            thread::spawn(move || {
+                let node_name = Uuid::new_v4().to_string();
+                let node = client.node()
+                    .create(create_options(&node_name, Direction::Output))
+                    .unwrap();
+                (node_name, node.id)
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        let (node_name, id) = handle.join().unwrap();
+        let node_by_id = client.node().get_by_id(id).unwrap();
+        assert_eq!(node_name, node_by_id.name);
+    }
+}
+
+#[rstest]
+#[serial]
+fn create_rejected_by_server(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node_name = Uuid::new_v4().to_string();
+    let options = create_options(&node_name, Direction::Output)
+        .with_factory_name("not-existing-factory".to_string());
+    let start = Instant::now();
+    let error = client.node()
+        .with_options(CallOptions::new().with_timeout(Duration::from_secs(10)))
+        .create(options)
+        .unwrap_err();
+    // Server error fails the request instead of letting it time out
+    assert_eq!(true, error.description.starts_with("Failed to create node:"));
+    assert_eq!(true, start.elapsed() < Duration::from_secs(10));
+}
+
+#[rstest]
+#[serial]
+fn create_with_options(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node_name = Uuid::new_v4().to_string();
+    let position = AudioChannelPosition::new(vec![
+        AudioChannel::FL, AudioChannel::FR, AudioChannel::FC, AudioChannel::LFE,
+        AudioChannel::SL, AudioChannel::SR, AudioChannel::FLC, AudioChannel::FRC,
+        AudioChannel::RC, AudioChannel::RL, AudioChannel::RR, AudioChannel::TC,
+        AudioChannel::TFL, AudioChannel::TFC, AudioChannel::TFR, AudioChannel::TRL,
+    ]);
+    let options = NodeCreateOptions::new(
+        node_name.clone(),
+        node_name.clone(),
+        node_name.clone(),
+        Direction::Output,
+        position
+    )
+        .with_sample_rate(48000)
+        .with_sample_format(AudioSampleFormat::F32_LE)
+        .with_linger(false)
+        .with_property("node.virtual".to_string(), "true".to_string());
+    let node = client.node()
+        .create(options)
+        .unwrap();
+    assert_eq!(node_name, node.name);
+    assert_eq!(16, node.format.unwrap().channels.default());
+}
+
+#[rstest]
+#[serial]
+fn create_with_reserved_property(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node_name = Uuid::new_v4().to_string();
+    let options = create_options(&node_name, Direction::Output)
+        .with_property(pipewire::keys::MEDIA_CLASS.to_string(), "Audio/Duplex".to_string());
+    let error = client.node()
+        .create(options)
+        .unwrap_err();
+    assert_eq!("Property(media.class) is set through node create options", error.description);
+}
+
+#[rstest]
+#[serial]
+fn create_with_empty_position(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node_name = Uuid::new_v4().to_string();
+    let options = NodeCreateOptions::new(
+        node_name.clone(),
+        node_name.clone(),
+        node_name.clone(),
+        Direction::Output,
+        AudioChannelPosition::default()
+    );
+    let error = client.node()
+        .create(options)
+        .unwrap_err();
+    assert_eq!("Node channel position cannot be empty", error.description);
+}
+
+#[rstest]
+#[serial]
+fn update_properties(
+    #[from(client2)] (client, observer): (PipewireTestClient, PipewireTestClient),
+) {
+    let node_name = Uuid::new_v4().to_string();
+    let node = client.node()
+        .create(create_options(&node_name, Direction::Output))
+        .unwrap();
+    let properties = HashMap::from([
+        (pipewire::keys::NODE_DESCRIPTION.to_string(), "updated description".to_string()),
+        (pipewire::keys::NODE_NICK.to_string(), "updated nickname".to_string()),
+    ]);
+    let node = client.node()
+        .update_properties(node.id, properties)
+        .unwrap();
+    assert_eq!(node_name, node.name);
+    assert_eq!("updated description", node.description);
+    assert_eq!("updated nickname", node.nickname);
+    // Fresh read through another connection, only fed by server info events
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let node = loop {
+        let node = observer.node()
+            .get(node_name.clone(), Direction::Output)
+            .unwrap();
+        if node.description == "updated description" || Instant::now() >= deadline {
+            break node;
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+    assert_eq!("updated description", node.description);
+    assert_eq!("updated nickname", node.nickname);
+}
+
+#[rstest]
+#[serial]
+fn update_properties_not_found(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let error = client.node()
+        .update_properties(u32::MAX, HashMap::new())
+        .unwrap_err();
+    assert_eq!(
+        format!("Node with id({}) not found", u32::MAX),
+        error.description
+    );
+}
+
+#[rstest]
+#[serial]
+fn create_returns_node_details(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node_name = Uuid::new_v4().to_string();
+    client.node()
+        .create(create_options(&node_name, Direction::Output))
+        .unwrap();
+    let node = client.node()
+        .get(node_name.clone(), Direction::Output)
+        .unwrap();
+    assert_ne!(NodeStatus::Creating, node.state);
+    assert_eq!(true, node.max_input_ports > 0);
+    assert_eq!(true, node.serial.is_some());
+    assert_eq!(true, node.client_id.is_some());
+    assert_eq!(
+        Some(&node_name),
+        node.properties.get(*pipewire::keys::NODE_NAME)
+    );
+}
+
+#[rstest]
+#[serial]
+fn video_node_details(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let name = "test-video-source".to_string();
+    let mut nodes = client.node()
+        .find(move |properties| {
+            properties.get(*pipewire::keys::NODE_NAME) == Some(&name)
+        })
+        .unwrap();
+    assert_eq!(1, nodes.len());
+    let node = nodes.remove(0);
+    assert_eq!(None, node.direction);
+    // Details are followed for every node, not only audio sink/source ones
+    assert_ne!(NodeStatus::Creating, node.state);
+    assert_eq!(true, node.max_output_ports > 0);
+    let listeners = client.core().get_listeners().unwrap();
+    let node_listeners = listeners.get(&TypeId::of::<NodeState>()).unwrap();
+    assert_eq!(Some(&vec!["info".to_string()]), node_listeners.get(&node.id.to_string()));
+}
+
+#[rstest]
+#[serial]
+fn create_from_channels_count(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node_name = Uuid::new_v4().to_string();
+    let options = NodeCreateOptions::from_channels_count(
+        node_name.clone(),
+        node_name.clone(),
+        node_name.clone(),
+        Direction::Output,
+        7
+    ).unwrap();
+    assert_eq!(
+        AudioChannelPosition::try_from(AudioChannelLayout::Surround7_0).unwrap(),
+        options.position
+    );
+    let node = client.node().create(options).unwrap();
+    assert_eq!(
+        Some(&"[ FL FR FC SL SR RL RR ]".to_string()),
+        node.properties.get("audio.position")
+    );
+    client.node().delete(node.id).unwrap();
+    let error = NodeCreateOptions::from_channels_count(
+        node_name.clone(),
+        node_name.clone(),
+        node_name.clone(),
+        Direction::Output,
+        9
+    ).unwrap_err();
+    assert_eq!("No usual layout for 9 channels", error.description);
+}
+
+#[rstest]
+#[serial]
+fn get_by_id(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node_name = Uuid::new_v4().to_string();
+    let node = client.node()
+        .create(create_options(&node_name, Direction::Output))
+        .unwrap();
+    let found_node = client.node()
+        .get_by_id(node.id)
+        .unwrap();
+    assert_eq!(node.id, found_node.id);
+    assert_eq!(node_name, found_node.name);
+}
+
+#[rstest]
+#[serial]
+fn get_by_serial(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node_name = Uuid::new_v4().to_string();
+    let node = client.node()
+        .create(create_options(&node_name, Direction::Output))
+        .unwrap();
+    let found_node = client.node()
+        .get_by_serial(node.serial.unwrap())
+        .unwrap();
+    assert_eq!(node.id, found_node.id);
+    let error = client.node()
+        .get_by_serial(u64::MAX)
+        .unwrap_err();
+    assert_eq!(
+        format!("Node with serial({}) not found", u64::MAX),
+        error.description
+    );
+}
+
+#[rstest]
+#[serial]
+fn find(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node_name = Uuid::new_v4().to_string();
+    client.node()
+        .create(create_options(&node_name, Direction::Input))
+        .unwrap();
+    let predicate_node_name = node_name.clone();
+    let nodes = client.node()
+        .find(move |properties| {
+            properties.get(*pipewire::keys::NODE_NAME) == Some(&predicate_node_name)
+        })
+        .unwrap();
+    assert_eq!(1, nodes.len());
+    assert_eq!(node_name, nodes[0].name);
+    let nodes = client.node()
+        .find(|_| false)
+        .unwrap();
+    assert_eq!(true, nodes.is_empty());
+}
+
+#[rstest]
+#[serial]
+fn find_video_node(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let nodes = client.node()
+        .find(|properties| {
+            properties.get(*pipewire::keys::NODE_NAME) == Some(&"test-video-source".to_string())
+        })
+        .unwrap();
+    assert_eq!(1, nodes.len());
+    assert_eq!(None, nodes[0].direction);
+    assert_eq!(true, nodes[0].format.is_none());
+    assert_eq!(false, nodes[0].is_default);
+    let node = client.node()
+        .get_by_id(nodes[0].id)
+        .unwrap();
+    assert_eq!("test-video-source", node.name);
+}
+
+#[rstest]
+#[serial]
+fn suspend_then_resume(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node_name = Uuid::new_v4().to_string();
+    let node = client.node()
+        .create(create_options(&node_name, Direction::Output))
+        .unwrap();
+    let state = client.node()
+        .suspend(node.id)
+        .unwrap();
+    assert_eq!(NodeStatus::Suspended, state);
+    let state = client.node()
+        .resume(node.id)
+        .unwrap();
+    assert_ne!(NodeStatus::Suspended, state);
+}
+
+#[rstest]
+#[serial]
+fn suspend_not_found(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let error = client.node()
+        .suspend(u32::MAX)
+        .unwrap_err();
+    assert_eq!(
+        format!("Node with id({}) not found", u32::MAX),
+        error.description
+    );
+}
@@ -1,19 +1,31 @@
 use crate::client::api::internal::InternalApi;
 use crate::error::Error;
+use crate::CallOptions;
 use crate::listeners::ListenerControlFlow;
 use crate::messages::{MessageRequest, MessageResponse, StreamCallback};
 use crate::states::GlobalId;
-use crate::{AudioStreamInfo, Direction};
+use crate::info::StreamInfo;
+use crate::{AudioStreamInfo, Direction, VideoStreamInfo};
+use pipewire_spa_utils::control::{ControlInfo, MidiEvent, SpaSequence};
 use std::sync::Arc;
 
 pub struct StreamApi {
     api: Arc<InternalApi>,
+    options: CallOptions,
 }
 
 impl StreamApi {
     pub(crate) fn new(api: Arc<InternalApi>) -> Self {
         StreamApi {
             api,
+            options: CallOptions::default(),
+        }
+    }
+
+    pub fn with_options(&self, options: CallOptions) -> Self {
+        StreamApi {
+            api: self.api.clone(),
+            options,
         }
     }
 
@@ -24,6 +36,114 @@ impl StreamApi {
         format: AudioStreamInfo,
         callback: F,
     ) -> Result<String, Error>
+    where
+        F: FnMut(&mut ListenerControlFlow, pipewire::buffer::Buffer) + Send + 'static
+    {
+        self.internal_create(node_id, direction, StreamInfo::Audio(format), callback)
+    }
+
+    /// Format choices (e.g. sizes range or formats enum) are negotiated with the peer on connection
+    pub fn create_video<F>(
+        &self,
+        node_id: u32,
+        direction: Direction,
+        format: VideoStreamInfo,
+        callback: F,
+    ) -> Result<String, Error>
+    where
+        F: FnMut(&mut ListenerControlFlow, pipewire::buffer::Buffer) + Send + 'static
+    {
+        self.internal_create(node_id, direction, StreamInfo::Video(format), callback)
+    }
+
+    /// Buffers are read as control sequences, callback receives their MIDI events
+    /// with offsets in samples from the start of the cycle
+    pub fn create_midi_input<F>(
+        &self,
+        node_id: u32,
+        mut callback: F,
+    ) -> Result<String, Error>
+    where
+        F: FnMut(&mut ListenerControlFlow, Vec<MidiEvent>) + Send + 'static
+    {
+        self.internal_create(
+            node_id,
+            Direction::Input,
+            StreamInfo::Midi(ControlInfo),
+            move |control_flow, mut buffer| {
+                let data = match buffer.datas_mut().first_mut() {
+                    Some(value) => value,
+                    None => return,
+                };
+                let offset = data.chunk().offset() as usize;
+                let size = data.chunk().size() as usize;
+                let bytes = match data.data() {
+                    Some(value) => value,
+                    None => return,
+                };
+                let sequence = match bytes.get(offset..offset + size) {
+                    Some(value) => SpaSequence::from_bytes(value),
+                    None => return,
+                };
+                // Empty chunk or invalid sequence, there is nothing to surface for this cycle
+                if let Ok(sequence) = sequence {
+                    callback(control_flow, sequence.midi_events());
+                }
+            }
+        )
+    }
+
+    /// Callback returns MIDI events to write for the cycle, with offsets in samples
+    /// from its start. Events not fitting in buffer are dropped.
+    pub fn create_midi_output<F>(
+        &self,
+        node_id: u32,
+        mut callback: F,
+    ) -> Result<String, Error>
+    where
+        F: FnMut(&mut ListenerControlFlow) -> Vec<MidiEvent> + Send + 'static
+    {
+        self.internal_create(
+            node_id,
+            Direction::Output,
+            StreamInfo::Midi(ControlInfo),
+            move |control_flow, mut buffer| {
+                let events = callback(control_flow);
+                let data = match buffer.datas_mut().first_mut() {
+                    Some(value) => value,
+                    None => return,
+                };
+                let sequences = [
+                    SpaSequence::from_midi_events(events),
+                    SpaSequence::default(),
+                ];
+                let value = match data.data() {
+                    Some(value) => value,
+                    None => return,
+                };
+                // Empty sequence is written when events don't fit
+                let capacity = value.len();
+                let bytes = sequences.iter()
+                    .filter_map(|sequence| sequence.to_bytes().ok())
+                    .find(|bytes| bytes.len() <= capacity)
+                    .unwrap_or_default();
+                value[..bytes.len()].copy_from_slice(&bytes);
+                let size = bytes.len();
+                let chunk = data.chunk_mut();
+                *chunk.offset_mut() = 0;
+                *chunk.size_mut() = size as u32;
+                *chunk.stride_mut() = 1;
+            }
+        )
+    }
+
+    fn internal_create<F>(
+        &self,
+        node_id: u32,
+        direction: Direction,
+        format: StreamInfo,
+        callback: F,
+    ) -> Result<String, Error>
     where
         F: FnMut(&mut ListenerControlFlow, pipewire::buffer::Buffer) + Send + 'static
     {
@@ -33,7 +153,7 @@ impl StreamApi {
             format,
             callback: StreamCallback::from(callback),
         };
-        let response = self.api.send_request(&request);
+        let response = self.api.send_request(&request, &self.options);
         match response {
             Ok(MessageResponse::CreateStream(name)) => Ok(name),
             Err(value) => Err(value),
@@ -48,7 +168,7 @@ impl StreamApi {
         name: String
     ) -> Result<(), Error> {
         let request = MessageRequest::DeleteStream(name);
-        let response = self.api.send_request(&request);
+        let response = self.api.send_request(&request, &self.options);
         match response {
             Ok(MessageResponse::DeleteStream) => Ok(()),
             Err(value) => Err(value),
@@ -63,7 +183,7 @@ impl StreamApi {
         name: String
     ) -> Result<(), Error> {
         let request = MessageRequest::ConnectStream(name);
-        let response = self.api.send_request(&request);
+        let response = self.api.send_request(&request, &self.options);
         match response {
             Ok(MessageResponse::ConnectStream) => Ok(()),
             Err(value) => Err(value),
@@ -78,7 +198,7 @@ impl StreamApi {
         name: String
     ) -> Result<(), Error> {
         let request = MessageRequest::DisconnectStream(name);
-        let response = self.api.send_request(&request);
+        let response = self.api.send_request(&request, &self.options);
         match response {
             Ok(MessageResponse::DisconnectStream) => Ok(()),
             Err(value) => Err(value),
@@ -87,4 +207,20 @@ impl StreamApi {
             }),
         }
     }
+
+    /// Format agreed with the peer once stream is connected, choices are fixated
+    pub fn format(
+        &self,
+        name: String
+    ) -> Result<StreamInfo, Error> {
+        let request = MessageRequest::GetStreamFormat(name);
+        let response = self.api.send_request(&request, &self.options);
+        match response {
+            Ok(MessageResponse::GetStreamFormat(value)) => Ok(value),
+            Err(value) => Err(value),
+            Ok(value) => Err(Error {
+                description: format!("Received unexpected response: {:?}", value),
+            }),
+        }
+    }
 }
\ No newline at end of file
@@ -1,15 +1,24 @@
 use crate::listeners::ListenerControlFlow;
 use crate::states::StreamState;
 use crate::test_utils::fixtures::{input_connected_stream, input_node, input_stream, output_connected_stream, output_node, output_stream, shared_client, ConnectedStreamFixture, NodeInfoFixture, PipewireTestClient, StreamFixture};
-use crate::{Direction, PipewireClient};
+use crate::{Direction, NodeInfo, PipewireClient};
 use rstest::rstest;
 use serial_test::serial;
 use std::any::TypeId;
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
+use std::time::Duration;
 use crate::client::api::StreamApi;
 use crate::client::CoreApi;
+use crate::{AudioStreamInfo, CallOptions, StreamInfo, VideoStreamInfo};
+use pipewire_spa_utils::audio::iec958::AudioInfoIec958;
+use pipewire_spa_utils::audio::AudioIec958Codec;
+use pipewire_spa_utils::control::MidiEvent;
+use pipewire_spa_utils::utils::choice::SpaChoice;
+use pipewire_spa_utils::video::raw::VideoInfoRaw;
+use pipewire_spa_utils::video::{VideoFormat, VideoFraction, VideoSize};
 
+/// Streams hold a format listener besides their process listener
 fn assert_listeners(client: &CoreApi, stream_name: &String, expected_listener: u32) {
     let listeners = client.get_listeners().unwrap();
     let stream_listeners = listeners.get(&TypeId::of::<StreamState>()).unwrap().iter()
@@ -25,6 +34,17 @@ fn assert_listeners(client: &CoreApi, stream_name: &String, expected_listener: u
     assert_eq!(expected_listener as usize, stream_listeners.len());
 }
 
+fn find_node(client: &PipewireClient, name: &str) -> NodeInfo {
+    let name = name.to_string();
+    let mut nodes = client.node()
+        .find(move |properties| {
+            properties.get(*pipewire::keys::NODE_NAME) == Some(&name)
+        })
+        .unwrap();
+    assert_eq!(1, nodes.len());
+    nodes.remove(0)
+}
+
 fn internal_create<F>(
     client: &StreamApi,
     node: &NodeInfoFixture,
@@ -38,7 +58,7 @@ where
         .create(
             node.id,
             direction,
-            node.format.clone().into(),
+            node.format.clone().unwrap().into(),
             callback
         )
         .unwrap();
@@ -63,7 +83,7 @@ fn abstract_create(
         Direction::Input => assert_eq!(true, stream.ends_with(".stream_input")),
         Direction::Output => assert_eq!(true, stream.ends_with(".stream_output"))
     };
-    assert_listeners(client.core(), &stream, 1);
+    assert_listeners(client.core(), &stream, 2);
 }
 
 #[rstest]
@@ -84,6 +104,160 @@ fn create_output(
     abstract_create(&node.client(), &node, direction);
 }
 
+#[rstest]
+#[serial]
+fn create_iec958(
+    #[from(output_node)] node: NodeInfoFixture
+) {
+    let format = AudioInfoIec958 {
+        codec: SpaChoice::Enum {
+            default: AudioIec958Codec::AC3,
+            alternatives: vec![AudioIec958Codec::AC3, AudioIec958Codec::DTS],
+        },
+        rate: Some(SpaChoice::None(48000)),
+    };
+    let stream = node.client().stream()
+        .create(
+            node.id,
+            Direction::Output,
+            AudioStreamInfo::Iec958(format),
+            move |control_flow, _| {
+                control_flow.release();
+            }
+        )
+        .unwrap();
+    assert_eq!(true, stream.ends_with(".stream_output"));
+    assert_listeners(node.client().core(), &stream, 2);
+    node.client().stream().delete(stream).unwrap();
+}
+
+#[rstest]
+#[serial]
+fn create_video(
+    #[from(input_node)] node: NodeInfoFixture
+) {
+    let format = VideoInfoRaw {
+        format: SpaChoice::Enum {
+            default: VideoFormat::RGBx,
+            alternatives: vec![VideoFormat::RGBx, VideoFormat::YUY2],
+        },
+        modifier: None,
+        size: SpaChoice::Range {
+            default: VideoSize::new(320, 240),
+            minimum: VideoSize::new(1, 1),
+            maximum: VideoSize::new(1920, 1080),
+        },
+        framerate: SpaChoice::None(VideoFraction::new(25, 1)),
+        max_framerate: None,
+    };
+    let stream = node.client().stream()
+        .create_video(
+            node.id,
+            Direction::Input,
+            VideoStreamInfo::Raw(format),
+            move |control_flow, _| {
+                control_flow.release();
+            }
+        )
+        .unwrap();
+    assert_eq!(true, stream.ends_with(".video_stream_input"));
+    assert_listeners(node.client().core(), &stream, 2);
+    node.client().stream().delete(stream).unwrap();
+}
+
+#[rstest]
+#[serial]
+fn create_video_negotiates_format(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node = find_node(&client, "test-video-source");
+    // Video test source only produces RGB and UYVY frames
+    let format = VideoInfoRaw {
+        format: SpaChoice::Enum {
+            default: VideoFormat::RGB,
+            alternatives: vec![VideoFormat::RGB, VideoFormat::UYVY],
+        },
+        modifier: None,
+        size: SpaChoice::Range {
+            default: VideoSize::new(320, 240),
+            minimum: VideoSize::new(1, 1),
+            maximum: VideoSize::new(1920, 1080),
+        },
+        framerate: SpaChoice::None(VideoFraction::new(25, 1)),
+        max_framerate: None,
+    };
+    let stream = client.stream()
+        .create_video(
+            node.id,
+            Direction::Input,
+            VideoStreamInfo::Raw(format),
+            move |_, _| {}
+        )
+        .unwrap();
+    client.stream().connect(stream.clone()).unwrap();
+    let format = client.stream()
+        .with_options(CallOptions::new().with_timeout(Duration::from_secs(5)))
+        .format(stream.clone())
+        .unwrap();
+    let format = match format {
+        StreamInfo::Video(VideoStreamInfo::Raw(value)) => value,
+        value => panic!("Unexpected negotiated format: {:?}", value),
+    };
+    assert_eq!(true, matches!(format.format, SpaChoice::None(VideoFormat::RGB) | SpaChoice::None(VideoFormat::UYVY)));
+    assert_eq!(SpaChoice::None(VideoSize::new(320, 240)), format.size);
+    client.stream().delete(stream).unwrap();
+}
+
+#[rstest]
+#[serial]
+fn create_midi_input(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node = find_node(&client, "test-midi-bridge");
+    let stream = client.stream()
+        .create_midi_input(
+            node.id,
+            move |control_flow, _| {
+                control_flow.release();
+            }
+        )
+        .unwrap();
+    assert_eq!(true, stream.ends_with(".midi_stream_input"));
+    assert_listeners(client.core(), &stream, 2);
+    client.stream().connect(stream.clone()).unwrap();
+    let format = client.stream()
+        .with_options(CallOptions::new().with_timeout(Duration::from_secs(5)))
+        .format(stream.clone())
+        .unwrap();
+    assert_eq!(true, matches!(format, StreamInfo::Midi(_)));
+    client.stream().delete(stream).unwrap();
+}
+
+#[rstest]
+#[serial]
+fn create_midi_output(
+    #[from(shared_client)] client: PipewireTestClient,
+) {
+    let node = find_node(&client, "test-midi-bridge");
+    let stream = client.stream()
+        .create_midi_output(
+            node.id,
+            move |_| {
+                vec![
+                    MidiEvent {
+                        offset: 0,
+                        data: vec![0x90, 0x3c, 0x7f],
+                    },
+                ]
+            }
+        )
+        .unwrap();
+    assert_eq!(true, stream.ends_with(".midi_stream_output"));
+    assert_listeners(client.core(), &stream, 2);
+    client.stream().connect(stream.clone()).unwrap();
+    client.stream().delete(stream).unwrap();
+}
+
 #[rstest]
 #[serial]
 fn create_twice(
@@ -94,7 +268,7 @@ fn create_twice(
         .create(
             node.id,
             direction.clone(),
-            node.format.clone().into(),
+            node.format.clone().unwrap().into(),
            move |_, _| {} 
         )
         .unwrap();
@@ -102,7 +276,7 @@ fn create_twice(
         .create(
             node.id,
             direction.clone(),
-            node.format.clone().into(),
+            node.format.clone().unwrap().into(),
             move |_, _| {}
         )
         .unwrap_err();
@@ -110,7 +284,7 @@ fn create_twice(
         format!("Stream with name({}) already exists", stream),
         error.description
     );
-    assert_listeners(node.client().core(), &stream, 1);
+    assert_listeners(node.client().core(), &stream, 2);
 }
 
 #[rstest]
@@ -161,7 +335,7 @@ fn connect_input(
     #[from(input_stream)] stream: StreamFixture
 ) {
     stream.connect().unwrap();
-    assert_listeners(stream.client().core(), &stream, 1);
+    assert_listeners(stream.client().core(), &stream, 2);
 }
 
 #[rstest]
@@ -170,7 +344,7 @@ fn connect_output(
     #[from(output_stream)] stream: StreamFixture
 ) {
     stream.connect().unwrap();
-    assert_listeners(stream.client().core(), &stream, 1);
+    assert_listeners(stream.client().core(), &stream, 2);
 }
 
 #[rstest]
@@ -191,7 +365,7 @@ fn disconnect_input(
     #[from(input_connected_stream)] stream: ConnectedStreamFixture
 ) {
     stream.disconnect().unwrap();
-    assert_listeners(stream.client().core(), &stream, 1);
+    assert_listeners(stream.client().core(), &stream, 2);
 }
 
 #[rstest]
@@ -200,7 +374,7 @@ fn disconnect_output(
     #[from(output_connected_stream)] stream: ConnectedStreamFixture
 ) {
     stream.disconnect().unwrap();
-    assert_listeners(stream.client().core(), &stream, 1);
+    assert_listeners(stream.client().core(), &stream, 2);
 }
 
 #[rstest]
@@ -1,14 +1,10 @@
-use ControlFlow::Break;
 use crate::error::Error;
-use crossbeam_channel::{unbounded, SendError, TryRecvError};
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::ops::ControlFlow;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::runtime::Runtime;
-use tokio::select;
-use tokio::time::Instant;
+use tokio::sync::{broadcast, oneshot};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
@@ -46,42 +42,111 @@ impl <T> Response<T> {
     }
 }
 
-type PendingMessages<R> = Arc<Mutex<HashMap<Uuid, Response<R>>>>;
-type GlobalMessages<R> = Arc<Mutex<Vec<Response<R>>>>;
-
 const GLOBAL_MESSAGE_ID: Uuid = Uuid::nil();
+/// Unread global messages are bounded, oldest ones are dropped first
+pub(super) const GLOBAL_MESSAGES_CAPACITY: usize = 64;
+
+/// Routes each response to the request waiting for it. Messages sent outside of a request
+/// context (i.e. with GLOBAL_MESSAGE_ID) are broadcast, e.g. initialization or registry errors.
+struct Dispatcher<R> {
+    pending_requests: Mutex<Option<HashMap<Uuid, oneshot::Sender<R>>>>,
+    global_sender: Mutex<Option<broadcast::Sender<R>>>,
+}
+
+impl <R: Clone> Dispatcher<R> {
+    fn new() -> (Self, broadcast::Receiver<R>) {
+        let (global_sender, global_receiver) = broadcast::channel(GLOBAL_MESSAGES_CAPACITY);
+        let dispatcher = Self {
+            pending_requests: Mutex::new(Some(HashMap::new())),
+            global_sender: Mutex::new(Some(global_sender)),
+        };
+        (dispatcher, global_receiver)
+    }
+
+    /// Must be called before request is sent, response could be dispatched before we wait for it
+    fn register(&self, id: Uuid) -> Result<oneshot::Receiver<R>, Error> {
+        let mut pending_requests = self.pending_requests.lock().unwrap();
+        match pending_requests.as_mut() {
+            Some(value) => {
+                let (sender, receiver) = oneshot::channel();
+                value.insert(id, sender);
+                Ok(receiver)
+            }
+            None => Err(Error {
+                description: "Channel disconnected".to_string(),
+            }),
+        }
+    }
+
+    fn unregister(&self, id: &Uuid) {
+        if let Some(value) = self.pending_requests.lock().unwrap().as_mut() {
+            value.remove(id);
+        }
+    }
+
+    /// Responses without a registered request (e.g. fired requests) are discarded
+    fn dispatch(&self, response: Response<R>) {
+        if response.id == GLOBAL_MESSAGE_ID {
+            if let Some(sender) = self.global_sender.lock().unwrap().as_ref() {
+                // Fails only without any receiver, then nobody is interested by that message
+                let _ = sender.send(response.message);
+            }
+            return;
+        }
+        let sender = match self.pending_requests.lock().unwrap().as_mut() {
+            Some(value) => value.remove(&response.id),
+            None => None,
+        };
+        if let Some(sender) = sender {
+            // Fails only when requester stopped waiting
+            let _ = sender.send(response.message);
+        }
+    }
+
+    /// Dropping senders wakes up every waiting request with a disconnection
+    fn close(&self) {
+        self.pending_requests.lock().unwrap().take();
+        self.global_sender.lock().unwrap().take();
+    }
+
+    fn pending_requests_count(&self) -> usize {
+        match self.pending_requests.lock().unwrap().as_ref() {
+            Some(value) => value.len(),
+            None => 0,
+        }
+    }
+}
 
 pub(crate) struct ClientChannel<Q, R> {
     sender: pipewire::channel::Sender<Request<Q>>,
-    receiver: crossbeam_channel::Receiver<Response<R>>,
-    pub(super) global_messages: GlobalMessages<R>,
-    pub(super) pending_messages: PendingMessages<R>,
+    dispatcher: Arc<Dispatcher<R>>,
+    global_receiver: Arc<Mutex<broadcast::Receiver<R>>>,
+    /// Global messages are drained through their own receiver, draining them never
+    /// consumes the ones waited for with receive_timeout (e.g. initialization)
+    drained_receiver: Arc<Mutex<broadcast::Receiver<R>>>,
     runtime: Arc<Runtime>
 }
 
-impl <Q: Debug + Send + 'static, R: Send + 'static> ClientChannel<Q, R> {
-    pub(self) fn new(
+impl <Q: Debug + Send + 'static, R: Clone + Send + 'static> ClientChannel<Q, R> {
+    fn new(
         sender: pipewire::channel::Sender<Request<Q>>,
-        receiver: crossbeam_channel::Receiver<Response<R>>,
+        dispatcher: Arc<Dispatcher<R>>,
+        global_receiver: broadcast::Receiver<R>,
         runtime: Arc<Runtime>
     ) -> Self {
         Self {
             sender,
-            receiver,
-            global_messages: Arc::new(Mutex::new(Vec::new())),
-            pending_messages: Arc::new(Mutex::new(HashMap::new())),
+            dispatcher,
+            drained_receiver: Arc::new(Mutex::new(global_receiver.resubscribe())),
+            global_receiver: Arc::new(Mutex::new(global_receiver)),
             runtime,
         }
     }
 
     pub fn fire(&self, request: Q) -> Result<Uuid, Error> {
-        let id = Uuid::new_v4();
-        let request = Request {
-            id: id.clone(),
-            message: request,
-        };
-        let response = self.sender.send(request);
-        match response {
+        let request = Request::new(request);
+        let id = request.id;
+        match self.sender.send(request) {
             Ok(_) => Ok(id),
             Err(value) => Err(Error {
                 description: format!("Failed to send request: {:?}", value.message),
@@ -89,172 +154,102 @@ impl <Q: Debug + Send + 'static, R: Send + 'static> ClientChannel<Q, R> {
         }
     }
 
-    pub fn send(&self, request: Q) -> Result<R, Error> {
-        let id = Uuid::new_v4();
-        let request = Request {
-            id: id.clone(),
-            message: request,
-        };
-        let response = self.sender.send(request);
-        let response = match response {
-            Ok(_) => self.receive(
-                id,
-                self.global_messages.clone(),
-                self.pending_messages.clone(),
-                self.receiver.clone(),
-                CancellationToken::default()
-            ),
-            Err(value) => return Err(Error {
-                description: format!("Failed to send request: {:?}", value.message),
-            }),
-        };
-        match response {
-            Ok(value) => Ok(value.message),
-            Err(value) => Err(Error {
-                description: format!(
-                    "Failed to execute request ({:?})", value
-                ),
-            }),
+    fn register_and_send(&self, request: Q) -> Result<(Uuid, oneshot::Receiver<R>), Error> {
+        let request = Request::new(request);
+        let id = request.id;
+        let receiver = self.dispatcher.register(id)?;
+        match self.sender.send(request) {
+            Ok(_) => Ok((id, receiver)),
+            Err(value) => {
+                self.dispatcher.unregister(&id);
+                Err(Error {
+                    description: format!("Failed to send request: {:?}", value.message),
+                })
+            }
         }
     }
 
     pub fn send_timeout(&self, request: Q, timeout: Duration) -> Result<R, Error> {
-        let request_id = match self.fire(request) {
-            Ok(value) => value,
-            Err(value) => return Err(value)
-        };
-        self.internal_receive_timeout(request_id, timeout)
-    }
-    
-    pub fn receive_timeout(&self, timeout: Duration) -> Result<R, Error> {
-        self.internal_receive_timeout(GLOBAL_MESSAGE_ID, timeout)
+        self.send_cancellable(request, timeout, None)
     }
 
-    fn internal_receive_timeout(&self, id: Uuid, timeout: Duration) -> Result<R, Error> {
-        let global_messages = self.global_messages.clone();
-        let pending_messages = self.pending_messages.clone();
-        let receiver = self.receiver.clone();
-        let handle = self.runtime.spawn(async move {
-            let start_time = Instant::now();
-            loop {
-                let control_flow = Self::internal_receive(
-                    id,
-                    global_messages.clone(),
-                    pending_messages.clone(),
-                    receiver.clone()
-                );
-                match control_flow.await {
-                    Break(value) => {
-                        return match value {
-                            Ok(value   ) => Ok(value.message),
-                            Err(value) => Err(value)
-                        };
-                    },
-                    _ => {
-                        let now_time = Instant::now();
-                        let delta_time = now_time - start_time;
-                        if delta_time >= timeout {
-                            return Err(Error {
-                                description: "Timeout".to_string(),
-                            });
-                        }
-                        continue
-                    },
+    /// Request is unregistered on timeout or cancellation, its late response will be discarded
+    pub fn send_cancellable(
+        &self,
+        request: Q,
+        timeout: Duration,
+        cancellation_token: Option<&CancellationToken>
+    ) -> Result<R, Error> {
+        if cancellation_token.is_some_and(|value| value.is_cancelled()) {
+            return Err(Error {
+                description: "Cancelled".to_string(),
+            });
+        }
+        let (id, receiver) = self.register_and_send(request)?;
+        let response = self.runtime.block_on(async {
+            let cancelled = async {
+                match cancellation_token {
+                    Some(value) => value.cancelled().await,
+                    None => std::future::pending().await,
                 }
+            };
+            tokio::select! {
+                biased;
+                response = receiver => response.map_err(|_| Error {
+                    description: "Channel disconnected".to_string(),
+                }),
+                _ = tokio::time::sleep(timeout) => Err(Error {
+                    description: "Timeout".to_string(),
+                }),
+                _ = cancelled => Err(Error {
+                    description: "Cancelled".to_string(),
+                }),
             }
         });
-        self.runtime.block_on(handle).unwrap()
+        if response.is_err() {
+            self.dispatcher.unregister(&id);
+        }
+        response
     }
 
-    fn receive(
-        &self,
-        id: Uuid,
-        global_messages: GlobalMessages<R>,
-        pending_messages: PendingMessages<R>,
-        receiver: crossbeam_channel::Receiver<Response<R>>,
-        cancellation_token: CancellationToken
-    ) -> Result<Response<R>, Error> {
-        let handle = self.runtime.spawn(async move {
+    pub fn receive_timeout(&self, timeout: Duration) -> Result<R, Error> {
+        let mut receiver = self.global_receiver.lock().unwrap();
+        let response = self.runtime.block_on(tokio::time::timeout(timeout, async {
             loop {
-                select! {
-                    _ = cancellation_token.cancelled() => (),
-                    control_flow = Self::internal_receive(
-                        id, 
-                        global_messages.clone(), 
-                        pending_messages.clone(), 
-                        receiver.clone()
-                    ) => {
-                        match control_flow {
-                            Break(value) => return value,
-                            _ => continue,
-                        }
-                    }
+                match receiver.recv().await {
+                    Ok(value) => return Ok(value),
+                    // Oldest messages were dropped, following ones are still worth reading
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return Err(Error {
+                        description: "Channel disconnected".to_string(),
+                    }),
                 }
             }
-        });
-        self.runtime.block_on(handle).unwrap()
-    }
-    
-    async fn internal_receive(
-        id: Uuid,
-        global_messages: GlobalMessages<R>,
-        pending_messages: PendingMessages<R>,
-        receiver: crossbeam_channel::Receiver<Response<R>>,
-    ) -> ControlFlow<Result<Response<R>, Error>, ()>
-    {
-        let response = receiver.try_recv();
+        }));
         match response {
-            Ok(value) => {
-                // When request id is equal Uuid::nil, 
-                // message is sent when unrecoverable error occurred outside of request context.
-                // But it's not necessary an error message, initialized message is global too.
-                //
-                // Those errors are sent in event handler, registry 
-                // and during server thread init phase.
-                // Might a good idea to find a better solution, because any request could fail
-                // but not because request is malformed or request result cannot be computed, but
-                // because somewhere else something bad happen.
-                //
-                // Maybe putting error messages into a vec which will be regularly watched by an async
-                // periodic task ? But that involve to create a thread/task in tokio and that task will  
-                // live during client lifetime (maybe for a long time).
-                //
-                // Maybe add a separate channel but same issue occur here. An async periodic task will
-                // watch if any error message spawned
-                // 
-                // For now, solution is simple:
-                //   1.1: Requested id is equal to response id
-                //        in that case we break the loop because that's the requested id
-                //   1.2: Requested id is not equal to response id but to global id
-                //        we store that response to further process
-                if value.id == id {
-                    Break(Ok(value))
-                }
-                else if value.id == GLOBAL_MESSAGE_ID {
-                    global_messages.lock().unwrap().push(value);
-                    ControlFlow::Continue(())
-                }
-                else {
-                    pending_messages.lock().unwrap().insert(value.id.clone(), value);
-                    ControlFlow::Continue(())
-                }
-            }
-            Err(value) => {
-                match value {
-                    TryRecvError::Empty => {
-                        match pending_messages.lock().unwrap().remove(&id) {
-                            Some(value) => Break(Ok(value)),
-                            None => ControlFlow::Continue(()),
-                        }
-                    }
-                    TryRecvError::Disconnected => {
-                        Break(Err(Error {
-                            description: "Channel disconnected".to_string(),
-                        }))
-                    }
-                }
+            Ok(value) => value,
+            Err(_) => Err(Error {
+                description: "Timeout".to_string(),
+            }),
+        }
+    }
+
+    /// Drain global messages without waiting, oldest ones were dropped if capacity was reached
+    pub fn receive_all(&self) -> Vec<R> {
+        let mut receiver = self.drained_receiver.lock().unwrap();
+        let mut messages = vec![];
+        loop {
+            match receiver.try_recv() {
+                Ok(value) => messages.push(value),
+                Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                Err(_) => break,
             }
         }
+        messages
+    }
+
+    pub(crate) fn pending_requests_count(&self) -> usize {
+        self.dispatcher.pending_requests_count()
     }
 }
 
@@ -262,26 +257,29 @@ impl <Q, R> Clone for ClientChannel<Q, R> {
     fn clone(&self) -> Self {
         Self {
             sender: self.sender.clone(),
-            receiver: self.receiver.clone(),
-            global_messages: self.global_messages.clone(),
-            pending_messages: self.pending_messages.clone(),
+            dispatcher: self.dispatcher.clone(),
+            global_receiver: self.global_receiver.clone(),
+            drained_receiver: self.drained_receiver.clone(),
             runtime: self.runtime.clone(),
         }
     }
 }
 
-pub(crate) struct ServerChannel<Q: 'static, R> {
-    sender: crossbeam_channel::Sender<Response<R>>,
+pub(crate) struct ServerChannel<Q: 'static, R: Clone> {
+    dispatcher: Arc<Dispatcher<R>>,
+    /// Shared by every server channel clone, the last one dropped closes the dispatcher
+    liveness: Arc<()>,
     receiver: Option<pipewire::channel::Receiver<Request<Q>>>
 }
 
-impl <Q, R> ServerChannel<Q, R> {
-    pub(self) fn new(
-        sender: crossbeam_channel::Sender<Response<R>>,
+impl <Q, R: Clone> ServerChannel<Q, R> {
+    fn new(
+        dispatcher: Arc<Dispatcher<R>>,
         receiver: pipewire::channel::Receiver<Request<Q>>,
     ) -> Self {
         Self {
-            sender,
+            dispatcher,
+            liveness: Arc::new(()),
             receiver: Some(receiver),
         }
     }
@@ -295,44 +293,57 @@ impl <Q, R> ServerChannel<Q, R> {
         attached_receiver
     }
     
-    pub fn fire(&self, response: R) -> Result<(), SendError<Response<R>>> {
+    pub fn fire(&self, response: R) -> Result<(), Error> {
         let response = Response {
             id: GLOBAL_MESSAGE_ID,
             message: response,
         };
-        self.sender.send(response)
+        self.dispatcher.dispatch(response);
+        Ok(())
     }
 
-    pub fn send(&self, request: &Request<Q>, response: R) -> Result<(), SendError<Response<R>>> {
+    pub fn send(&self, request: &Request<Q>, response: R) -> Result<(), Error> {
         let response = Response::from(request, response);
-        self.sender.send(response)
+        self.dispatcher.dispatch(response);
+        Ok(())
     }
 }
 
-impl <Q, R> Clone for ServerChannel<Q, R> {
+impl <Q, R: Clone> Clone for ServerChannel<Q, R> {
     fn clone(&self) -> Self {
         Self {
-            sender: self.sender.clone(),
+            dispatcher: self.dispatcher.clone(),
+            liveness: self.liveness.clone(),
             receiver: None // pipewire receiver cannot be cloned
         }
     }
 }
 
+impl <Q, R: Clone> Drop for ServerChannel<Q, R> {
+    fn drop(&mut self) {
+        if Arc::strong_count(&self.liveness) == 1 {
+            self.dispatcher.close();
+        }
+    }
+}
+
 pub(crate) fn channels<Q, R>(runtime: Arc<Runtime>) -> (ClientChannel<Q, R>, ServerChannel<Q, R>) 
 where 
     Q: Debug + Send, 
-    R: Send + 'static
+    R: Clone + Send + 'static
 {
     let (pw_sender, pw_receiver) = pipewire::channel::channel();
-    let (main_sender, main_receiver) = unbounded();
+    let (dispatcher, global_receiver) = Dispatcher::new();
+    let dispatcher = Arc::new(dispatcher);
     let client_channel = ClientChannel::<Q, R>::new(
         pw_sender,
-        main_receiver,
+        dispatcher.clone(),
+        global_receiver,
         runtime
     );
     let server_channel = ServerChannel::<Q, R>::new(
-        main_sender,
+        dispatcher,
         pw_receiver
     );
     (client_channel, server_channel)
-}
\ No newline at end of file
+}
@@ -1,7 +1,8 @@
-use crate::client::channel::{channels, Request};
+use crate::client::channel::{channels, Request, GLOBAL_MESSAGES_CAPACITY};
 use rstest::rstest;
 use std::thread;
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 use pipewire_test_utils::environment::TEST_ENVIRONMENT;
 
 #[derive(Debug, Clone, Copy)]
@@ -11,7 +12,7 @@ enum MessageRequest {
     Test2
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 enum MessageResponse {
     Test1,
     Test2,
@@ -48,13 +49,12 @@ fn request_context() {
     });
     let request_1 = MessageRequest::Test1;
     let request_2 = MessageRequest::Test2;
-    let response = client_channel.send(request_1).unwrap();
+    let response = client_channel.send_timeout(request_1, Duration::from_secs(1)).unwrap();
     assert_eq!(MessageResponse::Test1, response);
-    let response = client_channel.send(request_2).unwrap();
+    let response = client_channel.send_timeout(request_2, Duration::from_secs(1)).unwrap();
     assert_eq!(MessageResponse::Test2, response);
+    assert_eq!(0, client_channel.pending_requests_count());
     client_channel.fire(MessageRequest::Quit).unwrap();
-    assert_eq!(0, client_channel.global_messages.lock().unwrap().len());
-    assert_eq!(0, client_channel.pending_messages.lock().unwrap().len());
     handle_main.join().unwrap();
 }
 
@@ -67,13 +67,19 @@ fn global_message() {
             MessageRequest::Test2,
             Duration::from_millis(200),
         )
-        .unwrap_err(); 
-    assert_eq!(1, client_channel.global_messages.lock().unwrap().len());
-    assert_eq!(0, client_channel.pending_messages.lock().unwrap().len());
+        .unwrap_err();
+    assert_eq!(0, client_channel.pending_requests_count());
+    let response = client_channel
+        .receive_timeout(Duration::from_millis(200))
+        .unwrap();
+    assert_eq!(MessageResponse::GlobalMessage, response);
+    client_channel
+        .receive_timeout(Duration::from_millis(200))
+        .unwrap_err();
 }
 
 #[rstest]
-fn pending_message() {
+fn late_message() {
     let (client_channel, server_channel) = channels::<MessageRequest, MessageResponse>(TEST_ENVIRONMENT.lock().unwrap().runtime.clone());
     let request = Request::new(MessageRequest::Test1);
     server_channel.send(&request ,MessageResponse::Test1).unwrap();
@@ -83,6 +89,57 @@ fn pending_message() {
             Duration::from_millis(200),
         )
         .unwrap_err();
-    assert_eq!(0, client_channel.global_messages.lock().unwrap().len());
-    assert_eq!(1, client_channel.pending_messages.lock().unwrap().len());
+    assert_eq!(0, client_channel.pending_requests_count());
+    client_channel
+        .receive_timeout(Duration::from_millis(200))
+        .unwrap_err();
+}
+
+#[rstest]
+fn disconnected() {
+    let (client_channel, server_channel) = channels::<MessageRequest, MessageResponse>(TEST_ENVIRONMENT.lock().unwrap().runtime.clone());
+    drop(server_channel);
+    let error = client_channel
+        .receive_timeout(Duration::from_millis(200))
+        .unwrap_err();
+    assert_eq!("Channel disconnected", error.description);
+}
+
+#[rstest]
+fn cancelled() {
+    let (client_channel, _server_channel) = channels::<MessageRequest, MessageResponse>(TEST_ENVIRONMENT.lock().unwrap().runtime.clone());
+    let cancellation_token = CancellationToken::new();
+    cancellation_token.cancel();
+    let error = client_channel
+        .send_cancellable(
+            MessageRequest::Test1,
+            Duration::from_secs(10),
+            Some(&cancellation_token),
+        )
+        .unwrap_err();
+    assert_eq!("Cancelled", error.description);
+    assert_eq!(0, client_channel.pending_requests_count());
+}
+
+#[rstest]
+fn bounded_global_messages() {
+    let (client_channel, server_channel) = channels::<MessageRequest, MessageResponse>(TEST_ENVIRONMENT.lock().unwrap().runtime.clone());
+    for _ in 0..GLOBAL_MESSAGES_CAPACITY + 10 {
+        server_channel.fire(MessageResponse::GlobalMessage).unwrap();
+    }
+    let messages = client_channel.receive_all();
+    assert_eq!(GLOBAL_MESSAGES_CAPACITY, messages.len());
+    assert_eq!(0, client_channel.receive_all().len());
+}
+
+#[rstest]
+fn drained_global_messages() {
+    let (client_channel, server_channel) = channels::<MessageRequest, MessageResponse>(TEST_ENVIRONMENT.lock().unwrap().runtime.clone());
+    server_channel.fire(MessageResponse::GlobalMessage).unwrap();
+    assert_eq!(vec![MessageResponse::GlobalMessage], client_channel.receive_all());
+    // Draining doesn't consume message waited for
+    let response = client_channel
+        .receive_timeout(Duration::from_millis(200))
+        .unwrap();
+    assert_eq!(MessageResponse::GlobalMessage, response);
 }
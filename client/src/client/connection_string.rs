@@ -1,10 +1,11 @@
 use crate::constants::*;
+use crate::error::Error;
 use std::path::PathBuf;
 
 pub(super) struct PipewireClientSocketPath;
 
 impl PipewireClientSocketPath {
-    pub(super) fn from_env() -> PathBuf {
+    pub(super) fn from_env() -> Result<PathBuf, Error> {
         let pipewire_runtime_dir = std::env::var(PIPEWIRE_RUNTIME_DIR_ENVIRONMENT_KEY);
         let xdg_runtime_dir = std::env::var(XDG_RUNTIME_DIR_ENVIRONMENT_KEY);
 
@@ -12,26 +13,29 @@ impl PipewireClientSocketPath {
             (Ok(value), Ok(_)) => value,
             (Ok(value), Err(_)) => value,
             (Err(_), Ok(value)) => value,
-            (Err(_), Err(_)) => panic!(
-                "${} or ${} should be set. See https://docs.pipewire.org/page_man_pipewire_1.html",
-                PIPEWIRE_RUNTIME_DIR_ENVIRONMENT_KEY, XDG_RUNTIME_DIR_ENVIRONMENT_KEY
-            ),
+            (Err(_), Err(_)) => return Err(Error {
+                description: format!(
+                    "${} or ${} should be set. See https://docs.pipewire.org/page_man_pipewire_1.html",
+                    PIPEWIRE_RUNTIME_DIR_ENVIRONMENT_KEY, XDG_RUNTIME_DIR_ENVIRONMENT_KEY
+                ),
+            }),
         };
 
         let pipewire_remote = match std::env::var(PIPEWIRE_REMOTE_ENVIRONMENT_KEY) {
             Ok(value) => value,
-            Err(_) => panic!(
-                "${PIPEWIRE_REMOTE_ENVIRONMENT_KEY} should be set. See https://docs.pipewire.org/page_man_pipewire_1.html",
-            )
+            Err(_) => return Err(Error {
+                description: format!(
+                    "${PIPEWIRE_REMOTE_ENVIRONMENT_KEY} should be set. See https://docs.pipewire.org/page_man_pipewire_1.html",
+                ),
+            }),
         };
 
         let socket_path = PathBuf::from(socket_directory).join(pipewire_remote);
-        socket_path
+        Ok(socket_path)
     }
 }
 
 pub(super) struct PipewireClientInfo {
     pub name: String,
-    pub socket_location: String,
-    pub socket_name: String,
+    pub socket_path: PathBuf,
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use crate::constants::{METADATA_NAME_PROPERTY_VALUE_DEFAULT, METADATA_NAME_PROPERTY_VALUE_SETTINGS};
 use crate::error::Error;
+use crate::info::{NodeStatus, StreamInfo};
 use crate::messages::{EventMessage, MessageRequest, MessageResponse};
 use crate::states::{DefaultAudioNodesState, GlobalId, GlobalState, SettingsState};
 use pipewire_spa_utils::audio::raw::AudioInfoRaw;
@@ -15,7 +16,20 @@ pub(super) fn event_handler(
     event_sender: pipewire::channel::Sender<EventMessage>,
 ) -> impl Fn(EventMessage) + 'static
 {    
-    move |event_message: EventMessage| match event_message {
+    move |event_message: EventMessage| {
+        state.lock().unwrap().prune_listeners();
+        handle_event(event_message, state.clone(), server_channel.clone(), event_sender.clone())
+    }
+}
+
+fn handle_event(
+    event_message: EventMessage,
+    state: Arc<Mutex<GlobalState>>,
+    server_channel: ServerChannel<MessageRequest, MessageResponse>,
+    event_sender: pipewire::channel::Sender<EventMessage>,
+)
+{
+    match event_message {
         EventMessage::SetMetadataListeners { id } => handle_set_metadata_listeners(
             id,
             state.clone(),
@@ -53,6 +67,31 @@ pub(super) fn event_handler(
             state.clone(),
             server_channel.clone()
         ),
+        EventMessage::SetNodeFormatError { id, error } => handle_set_node_format_error(
+            id,
+            error,
+            state.clone(),
+            server_channel.clone()
+        ),
+        EventMessage::SetNodeInfo {
+            id,
+            status,
+            max_input_ports,
+            max_output_ports,
+            properties
+        } => handle_set_node_info(
+            id,
+            status,
+            max_input_ports,
+            max_output_ports,
+            properties,
+            state.clone(),
+        ),
+        EventMessage::SetStreamFormat { name, format } => handle_set_stream_format(
+            name,
+            format,
+            state.clone(),
+        ),
     }
 }
 
@@ -111,6 +150,7 @@ fn handle_remove_node(
         }
     };
     state.remove(&id);
+    state.notify_waiters();
 }
 fn handle_set_node_properties_listener(
     id: GlobalId,
@@ -129,6 +169,7 @@ fn handle_set_node_properties_listener(
             return;
         }
     };
+    let details_event_sender = event_sender.clone();
     let event_sender = event_sender.clone();
     node.add_properties_listener(
         move |control_flow, properties| {
@@ -168,6 +209,19 @@ fn handle_set_node_properties_listener(
             control_flow.release();
         }
     );
+    node.add_details_listener(
+        move |_, status, max_input_ports, max_output_ports, properties| {
+            details_event_sender
+                .send(EventMessage::SetNodeInfo {
+                    id: id.clone(),
+                    status,
+                    max_input_ports,
+                    max_output_ports,
+                    properties,
+                })
+                .unwrap();
+        }
+    );
 }
 fn handle_set_node_format_listener(
     id: GlobalId,
@@ -186,7 +240,11 @@ fn handle_set_node_format_listener(
             return;
         }
     };
-    let server_channel = server_channel.clone();
+    // Only audio sink/source nodes have their format retrieved, any other EnumFormat is skipped
+    // and listener would never be released
+    if node.direction().is_none() {
+        return;
+    }
     let event_sender = event_sender.clone();
     node.add_format_listener(
         move |control_flow, format| {
@@ -200,8 +258,11 @@ fn handle_set_node_format_listener(
                         .unwrap();
                 }
                 Err(value) => {
-                    server_channel
-                        .fire(MessageResponse::Error(value))
+                    event_sender
+                        .send(EventMessage::SetNodeFormatError {
+                            id,
+                            error: value,
+                        })
                         .unwrap();
                 }
             };
@@ -227,6 +288,7 @@ fn handle_set_node_properties(
         }
     };
     node.set_properties(properties);
+    state.notify_waiters();
 }
 fn handle_set_node_format(
     id: GlobalId,
@@ -246,4 +308,64 @@ fn handle_set_node_format(
         }
     };
     node.set_format(format);
-}
\ No newline at end of file
+    state.notify_waiters();
+}
+fn handle_set_node_format_error(
+    id: GlobalId,
+    error: Error,
+    state: Arc<Mutex<GlobalState>>,
+    server_channel: ServerChannel<MessageRequest, MessageResponse>,
+)
+{
+    let mut state = state.lock().unwrap();
+    let node = match state.get_node_mut(&id) {
+        Ok(value) => value,
+        Err(value) => {
+            server_channel
+                .fire(MessageResponse::Error(value))
+                .unwrap();
+            return;
+        }
+    };
+    // Node would stay pending forever otherwise, readiness must not wait for it
+    node.set_format_error(error.clone());
+    state.notify_waiters();
+    server_channel
+        .fire(MessageResponse::Error(error))
+        .unwrap();
+}
+fn handle_set_node_info(
+    id: GlobalId,
+    status: NodeStatus,
+    max_input_ports: u32,
+    max_output_ports: u32,
+    properties: Option<HashMap<String, String>>,
+    state: Arc<Mutex<GlobalState>>,
+)
+{
+    let mut state = state.lock().unwrap();
+    // Node could have been removed in between (i.e. "object.register" set to "false"),
+    // info events are emitted during all node lifetime, so we silently ignore them.
+    let Ok(node) = state.get_node_mut(&id) else {
+        return;
+    };
+    node.set_info(status, max_input_ports, max_output_ports);
+    if let Some(properties) = properties {
+        node.set_properties(properties);
+    }
+    state.notify_waiters();
+}
+fn handle_set_stream_format(
+    name: String,
+    format: StreamInfo,
+    state: Arc<Mutex<GlobalState>>,
+)
+{
+    let mut state = state.lock().unwrap();
+    // Stream could have been deleted in between, format is not relevant anymore
+    let Ok(stream) = state.get_stream_mut(&name) else {
+        return;
+    };
+    stream.set_negotiated_format(format);
+    state.notify_waiters();
+}
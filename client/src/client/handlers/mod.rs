@@ -3,3 +3,6 @@ mod registry;
 mod request;
 mod thread;
 pub use thread::pw_thread as thread;
+pub(crate) use thread::attach;
+pub(crate) use thread::PipewireThreadLoop;
+pub use thread::PipewireLoopAttachment;
@@ -1,5 +1,5 @@
 use crate::client::channel::ServerChannel;
-use crate::constants::{APPLICATION_NAME_PROPERTY_KEY, APPLICATION_NAME_PROPERTY_VALUE_PIPEWIRE_MEDIA_SESSION, APPLICATION_NAME_PROPERTY_VALUE_WIRE_PLUMBER, MEDIA_CLASS_PROPERTY_KEY, MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SINK, MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SOURCE, METADATA_NAME_PROPERTY_KEY, METADATA_NAME_PROPERTY_VALUE_DEFAULT, METADATA_NAME_PROPERTY_VALUE_SETTINGS};
+use crate::constants::{APPLICATION_NAME_PROPERTY_KEY, APPLICATION_NAME_PROPERTY_VALUE_PIPEWIRE_MEDIA_SESSION, APPLICATION_NAME_PROPERTY_VALUE_WIRE_PLUMBER, METADATA_NAME_PROPERTY_KEY, METADATA_NAME_PROPERTY_VALUE_DEFAULT, METADATA_NAME_PROPERTY_VALUE_SETTINGS};
 use crate::messages::{EventMessage, MessageRequest, MessageResponse};
 use crate::states::{ClientState, GlobalId, GlobalObjectState, GlobalState, MetadataState, NodeState};
 use pipewire::registry::GlobalObject;
@@ -138,14 +138,8 @@ fn handle_node(
         return;
     }
     let properties = global.props.unwrap();
-    let mut node = match properties.get(MEDIA_CLASS_PROPERTY_KEY) {
-        Some(MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SOURCE)
-        | Some(MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SINK) => {
-            let node: pipewire::node::Node = registry.bind(global).unwrap();
-            NodeState::new(node)
-        }
-        _ => return,
-    };
+    let node: pipewire::node::Node = registry.bind(global).unwrap();
+    let mut node = NodeState::new(node);
     node.set_properties(dict_ref_to_hashmap(properties));
     let mut state = state.lock().unwrap();
     if let Err(value) = state.insert_node(global.id.into(), node) {
@@ -154,10 +148,8 @@ fn handle_node(
             .unwrap();
         return;
     };
-    let node = state.get_node(&global.id.into()).unwrap();
     add_node_listeners(
         global.id.into(),
-        &node,
         &event_sender
     );
 }
@@ -257,14 +249,13 @@ fn add_metadata_listeners(
         .unwrap()
 }
 
+/// Newly bound node always gets its listeners: properties known from registry could already
+/// make it initialized (e.g. video or MIDI nodes have no format to retrieve), its details
+/// still have to be followed.
 fn add_node_listeners(
     id: GlobalId,
-    node: &NodeState,
     event_sender: &pipewire::channel::Sender<EventMessage>
 ) {
-    if node.state() != GlobalObjectState::Pending {
-        return;
-    }
     let id = id.clone();
     event_sender
         .send(EventMessage::SetNodePropertiesListener {
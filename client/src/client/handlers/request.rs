@@ -2,15 +2,14 @@ use crate::client::channel::{Request, ServerChannel};
 use crate::constants::*;
 use crate::error::Error;
 use crate::listeners::PipewireCoreSync;
-use crate::messages::{MessageRequest, MessageResponse, StreamCallback};
-use crate::states::{GlobalId, GlobalObjectState, GlobalState, NodeState, OrphanState, StreamState};
-use crate::{AudioStreamInfo, Direction, NodeInfo};
+use crate::messages::{EventMessage, MessageRequest, MessageResponse, NodePredicate, StreamCallback};
+use crate::states::{DefaultAudioNodesState, GlobalId, GlobalObjectState, GlobalState, NodeState, OrphanState, StateCondition, StreamState};
+use crate::info::{ShutdownReport, StreamInfo};
+use crate::{Direction, NodeCreateOptions, NodeInfo, ShutdownOptions};
 use pipewire::proxy::ProxyT;
 use std::cell::RefCell;
-use std::rc::Rc;
-
-#[cfg(test)]
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use pipewire_common::utils::dict_ref_to_hashmap;
 
@@ -18,31 +17,40 @@ struct Context {
     request: Request<MessageRequest>,
     core: Rc<pipewire::core::Core>,
     core_sync: Rc<PipewireCoreSync>,
-    main_loop: pipewire::main_loop::MainLoop,
     state: Arc<Mutex<GlobalState>>,
     server_channel: ServerChannel<MessageRequest, MessageResponse>,
+    event_sender: pipewire::channel::Sender<EventMessage>,
 }
 
 pub(super) fn request_handler(
     core: Rc<pipewire::core::Core>,
     core_sync: Rc<PipewireCoreSync>,
-    main_loop: pipewire::main_loop::MainLoop,
+    quit: impl Fn() + 'static,
     state: Arc<Mutex<GlobalState>>,
     server_channel: ServerChannel<MessageRequest, MessageResponse>,
+    event_sender: pipewire::channel::Sender<EventMessage>,
 ) -> impl Fn(Request<MessageRequest>) + 'static
 {
     move |request| {
+        // Requests are handled outside any PipeWire event, listeners released since last one
+        // can be dropped safely
+        state.lock().unwrap().prune_listeners();
+        core_sync.prune_listeners();
         let message_request = request.message.clone();
         let context = Context {
             request,
             core: core.clone(),
             core_sync: core_sync.clone(),
-            main_loop: main_loop.clone(),
             state: state.clone(),
             server_channel: server_channel.clone(),
+            event_sender: event_sender.clone(),
         };
         match message_request {
-            MessageRequest::Quit => main_loop.quit(),
+            MessageRequest::Quit => quit(),
+            MessageRequest::Shutdown(options) => handle_shutdown(
+                context,
+                options,
+            ),
             MessageRequest::Settings => handle_settings(
                 context,
             ),
@@ -53,21 +61,42 @@ pub(super) fn request_handler(
                 name,
                 direction
             } => handle_get_node(context, name, direction),
-            MessageRequest::CreateNode {
-                name,
-                description,
-                nickname,
-                direction,
-                channels,
-            } => handle_create_node(
+            MessageRequest::GetNodeById(id) => handle_get_node_by_id(context, id),
+            MessageRequest::GetNodeBySerial(serial) => handle_get_node_by_serial(
                 context,
-                name,
-                description,
-                nickname,
-                direction,
-                channels,
+                serial,
+            ),
+            MessageRequest::FindNodes(predicate) => handle_find_nodes(
+                context,
+                predicate,
+            ),
+            MessageRequest::CreateNode(options) => handle_create_node(
+                context,
+                options,
             ),
             MessageRequest::DeleteNode(id) => handle_delete_node(context, id),
+            MessageRequest::UpdateNodeProperties {
+                id,
+                properties
+            } => handle_update_node_properties(
+                context,
+                id,
+                properties,
+            ),
+            MessageRequest::SuspendNode(id) => handle_send_node_command(
+                context,
+                id,
+                pipewire::spa::sys::SPA_NODE_COMMAND_Suspend,
+                true,
+                MessageResponse::SuspendNode,
+            ),
+            MessageRequest::ResumeNode(id) => handle_send_node_command(
+                context,
+                id,
+                pipewire::spa::sys::SPA_NODE_COMMAND_Start,
+                false,
+                MessageResponse::ResumeNode,
+            ),
             MessageRequest::EnumerateNodes(direction) => handle_enumerate_node(
                 context,
                 direction,
@@ -96,34 +125,83 @@ pub(super) fn request_handler(
                 context,
                 name,
             ),
+            MessageRequest::GetStreamFormat(name) => handle_get_stream_format(
+                context,
+                name,
+            ),
             // Internal requests
             MessageRequest::CheckSessionManagerRegistered => handle_check_session_manager_registered(
                 context,
             ),
-            MessageRequest::SettingsState => handle_settings_state(
+            MessageRequest::WaitNodeInitialized(id) => handle_wait_node_initialized(
                 context,
+                id,
             ),
-            MessageRequest::DefaultAudioNodesState => handle_default_audio_nodes_state(
+            MessageRequest::WaitReady => handle_wait_ready(
                 context,
             ),
-            MessageRequest::NodeState(id) => handle_node_state(
+            MessageRequest::NodeCount => handle_node_count(
                 context,
-                id,
             ),
-            MessageRequest::NodeStates => handle_node_states(
+            MessageRequest::Diagnostics => handle_diagnostics(
                 context,
             ),
-            MessageRequest::NodeCount => handle_node_count(
+            #[cfg(test)]
+            MessageRequest::Listeners => handle_listeners(
                 context,
             ),
             #[cfg(test)]
-            MessageRequest::Listeners => handle_listeners(
+            MessageRequest::ProvokeCoreError => handle_provoke_core_error(
                 context,
             ),
+            #[cfg(test)]
+            MessageRequest::ProvokeNodeFormatError(id) => handle_provoke_node_format_error(
+                context,
+                id,
+            ),
         }
     }
 }
 
+fn handle_shutdown(
+    context: Context,
+    options: ShutdownOptions,
+)
+{
+    let mut state = context.state.lock().unwrap();
+    let mut report = ShutdownReport::default();
+    let stream_names = state.get_streams()
+        .map(|streams| streams.keys().map(|name| (*name).clone()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    for name in stream_names {
+        let stream = state.get_stream_mut(&name).unwrap();
+        if stream.is_connected() {
+            if let Err(value) = stream.disconnect() {
+                report.errors.push(value);
+            }
+        }
+        state.delete_stream(&name).unwrap();
+        report.streams.push(name);
+    }
+    for id in state.get_client_node_ids() {
+        // Bound but not yet registered nodes are released with orphans
+        let Ok(node) = state.take_node(&id) else {
+            continue;
+        };
+        if options.keep_lingering_nodes && node.is_lingering() {
+            report.lingering_nodes.push(id.into());
+            continue;
+        }
+        match node.destroy(&context.core) {
+            Ok(_) => report.destroyed_nodes.push(id.into()),
+            Err(value) => report.errors.push(value),
+        }
+    }
+    report.orphans = state.clear_orphans();
+    context.server_channel
+        .send(&context.request, MessageResponse::Shutdown(report))
+        .unwrap();
+}
 fn handle_settings(
     context: Context,
 ) 
@@ -144,6 +222,65 @@ fn handle_default_audio_nodes(
         .send(&context.request, MessageResponse::DefaultAudioNodes(default_audio_devices))
         .unwrap();
 }
+fn map_node_info(
+    id: &GlobalId,
+    node: &NodeState,
+    default_audio_nodes: &DefaultAudioNodesState,
+) -> Result<NodeInfo, Error>
+{
+    let properties = match node.properties() {
+        Some(value) => value,
+        None => return Err(Error {
+            description: format!("Node with id({}) has no properties", id),
+        })
+    };
+    let name = node.name()?;
+    let direction = node.direction();
+    let format = node.format();
+    if direction.is_some() && format.is_none() {
+        return Err(Error {
+            description: format!("Node with id({}) has no format", id),
+        });
+    }
+    let description = match (properties.get(*pipewire::keys::NODE_DESCRIPTION), &direction) {
+        (Some(value), _) => value.clone(),
+        (None, None) => name.clone(),
+        (None, Some(_)) => return Err(Error {
+            description: format!("Node with id({}) has no description", id),
+        })
+    };
+    let nickname = match properties.contains_key(*pipewire::keys::NODE_NICK) {
+        true => properties.get(*pipewire::keys::NODE_NICK).unwrap().clone(),
+        false => name.clone(),
+    };
+    let is_default = match direction {
+        Some(Direction::Input) => name == default_audio_nodes.source,
+        Some(Direction::Output) => name == default_audio_nodes.sink,
+        None => false,
+    };
+    let device_id = properties.get(*pipewire::keys::DEVICE_ID)
+        .and_then(|value| value.parse::<u32>().ok());
+    let serial = properties.get(*pipewire::keys::OBJECT_SERIAL)
+        .and_then(|value| value.parse::<u64>().ok());
+    let client_id = properties.get(*pipewire::keys::CLIENT_ID)
+        .and_then(|value| value.parse::<u32>().ok());
+    Ok(NodeInfo {
+        id: (*id).clone().into(),
+        name,
+        description,
+        nickname,
+        direction,
+        is_default,
+        format,
+        state: node.status(),
+        max_input_ports: node.max_input_ports(),
+        max_output_ports: node.max_output_ports(),
+        device_id,
+        serial,
+        client_id,
+        properties,
+    })
+}
 fn handle_get_node(
     context: Context,
     name: String,
@@ -153,10 +290,6 @@ fn handle_get_node(
     let control_flow = RefCell::new(false);
     let state = context.state.lock().unwrap();
     let default_audio_nodes = state.get_default_audio_nodes();
-    let default_audio_node = match direction {
-        Direction::Input => default_audio_nodes.source.clone(),
-        Direction::Output => default_audio_nodes.sink.clone()
-    };
     let nodes = match state.get_nodes() {
         Ok(value) => value,
         Err(value) => {
@@ -167,9 +300,10 @@ fn handle_get_node(
         }
     };
     let node = nodes.iter()
-        .find_map(|(id, node)| {
-            let properties = node.properties().unwrap();
-            let format = node.format().unwrap();
+        .find(|(_, node)| {
+            if *control_flow.borrow() == true {
+                return false;
+            }
             let name_to_compare = match node.name() {
                 Ok(value) => value,
                 Err(value) => {
@@ -177,54 +311,24 @@ fn handle_get_node(
                     context.server_channel
                         .send(&context.request, MessageResponse::Error(value))
                         .unwrap();
-                    return None;
-                }
-            };
-            let direction_to_compare = match node.direction() {
-                Ok(value) => value,
-                Err(value) => {
-                    control_flow.replace(true);
-                    context.server_channel
-                        .send(&context.request, MessageResponse::Error(value))
-                        .unwrap();
-                    return None;
+                    return false;
                 }
             };
-            if name_to_compare == name && direction_to_compare == direction {
-                Some((id, properties, format))
-            } else {
-                None
-            }
-        })
-        .iter()
-        .find_map(|(id, properties, format)| {
-            if *control_flow.borrow() == true {
-                return None;
-            }
-            let name = properties.get(*pipewire::keys::NODE_NAME).unwrap().clone();
-            let description = properties
-                .get(*pipewire::keys::NODE_DESCRIPTION)
-                .unwrap()
-                .clone();
-            let nickname = match properties.contains_key(*pipewire::keys::NODE_NICK) {
-                true => properties.get(*pipewire::keys::NODE_NICK).unwrap().clone(),
-                false => name.clone(),
-            };
-            let is_default = name == default_audio_node;
-            Some(NodeInfo {
-                id: (**id).clone().into(),
-                name,
-                description,
-                nickname,
-                direction: direction.clone(),
-                is_default,
-                format: format.clone()
-            })
+            name_to_compare == name && node.direction() == Some(direction.clone())
         });
+    if *control_flow.borrow() == true {
+        return;
+    }
     match node {
-        Some(value) => context.server_channel
-            .send(&context.request, MessageResponse::GetNode(value))
-            .unwrap(),
+        Some((id, node)) => {
+            let response = match map_node_info(id, node, &default_audio_nodes) {
+                Ok(value) => MessageResponse::GetNode(value),
+                Err(value) => MessageResponse::Error(value)
+            };
+            context.server_channel
+                .send(&context.request, response)
+                .unwrap()
+        },
         None => context.server_channel
             .send(&context.request, MessageResponse::Error(Error {
                 description: format!("Node with name({}) not found", name),
@@ -233,15 +337,136 @@ fn handle_get_node(
     }
 
 }
+fn handle_get_node_by_id(
+    context: Context,
+    id: GlobalId,
+)
+{
+    let state = context.state.lock().unwrap();
+    let default_audio_nodes = state.get_default_audio_nodes();
+    let response = match state.get_node(&id) {
+        Ok(node) => match map_node_info(&id, node, &default_audio_nodes) {
+            Ok(value) => MessageResponse::GetNode(value),
+            Err(value) => MessageResponse::Error(value)
+        },
+        Err(value) => MessageResponse::Error(value)
+    };
+    context.server_channel
+        .send(&context.request, response)
+        .unwrap();
+}
+fn handle_get_node_by_serial(
+    context: Context,
+    serial: u64,
+)
+{
+    let state = context.state.lock().unwrap();
+    let default_audio_nodes = state.get_default_audio_nodes();
+    let nodes = match state.get_nodes() {
+        Ok(value) => value,
+        Err(value) => {
+            context.server_channel
+                .send(&context.request, MessageResponse::Error(value))
+                .unwrap();
+            return;
+        }
+    };
+    let node = nodes.iter()
+        .find(|(_, node)| {
+            node.properties()
+                .and_then(|properties| properties.get(*pipewire::keys::OBJECT_SERIAL).cloned())
+                .and_then(|value| value.parse::<u64>().ok())
+                .is_some_and(|value| value == serial)
+        });
+    let response = match node {
+        Some((id, node)) => match map_node_info(id, node, &default_audio_nodes) {
+            Ok(value) => MessageResponse::GetNode(value),
+            Err(value) => MessageResponse::Error(value)
+        },
+        None => MessageResponse::Error(Error {
+            description: format!("Node with serial({}) not found", serial),
+        })
+    };
+    context.server_channel
+        .send(&context.request, response)
+        .unwrap();
+}
+fn handle_find_nodes(
+    context: Context,
+    predicate: NodePredicate,
+)
+{
+    let state = context.state.lock().unwrap();
+    let default_audio_nodes = state.get_default_audio_nodes();
+    let nodes = match state.get_nodes() {
+        Ok(value) => value,
+        Err(value) => {
+            context.server_channel
+                .send(&context.request, MessageResponse::Error(value))
+                .unwrap();
+            return;
+        }
+    };
+    // Pending nodes are skipped, they don't have a format yet
+    let nodes: Result<Vec<NodeInfo>, Error> = nodes
+        .iter()
+        .filter(|(_, node)| node.state() == GlobalObjectState::Initialized)
+        .filter(|(_, node)| {
+            node.properties()
+                .is_some_and(|properties| predicate.call(&properties))
+        })
+        .map(|(id, node)| map_node_info(id, node, &default_audio_nodes))
+        .collect();
+    let response = match nodes {
+        Ok(value) => MessageResponse::FindNodes(value),
+        Err(value) => MessageResponse::Error(value)
+    };
+    context.server_channel
+        .send(&context.request, response)
+        .unwrap();
+}
 fn handle_create_node(
     context: Context,
-    name: String,
-    description: String,
-    nickname: String,
-    direction: Direction,
-    channels: u16,
+    options: NodeCreateOptions,
 ) 
 {
+    let name = options.name.clone();
+    let direction = options.direction.clone();
+    if options.position.is_empty() {
+        context.server_channel
+            .send(
+                &context.request,
+                MessageResponse::Error(Error {
+                    description: "Node channel position cannot be empty".to_string(),
+                })
+            )
+            .unwrap();
+        return;
+    }
+    // Typed options own these properties, node lookup relies on some of them (e.g. node.name)
+    let reserved_keys = [
+        *pipewire::keys::FACTORY_NAME,
+        *pipewire::keys::NODE_NAME,
+        *pipewire::keys::NODE_DESCRIPTION,
+        *pipewire::keys::NODE_NICK,
+        *pipewire::keys::MEDIA_CLASS,
+        *pipewire::keys::OBJECT_LINGER,
+        *pipewire::keys::AUDIO_CHANNELS,
+        *pipewire::keys::AUDIO_RATE,
+        *pipewire::keys::AUDIO_FORMAT,
+        AUDIO_POSITION_PROPERTY_KEY,
+    ];
+    if let Some(key) = options.properties.keys().find(|key| reserved_keys.contains(&key.as_str())) {
+        context.server_channel
+            .send(
+                &context.request,
+                MessageResponse::Error(Error {
+                    description: format!("Property({}) is set through node create options", key),
+                })
+            )
+            .unwrap();
+        return;
+    }
     {
         let control_flow = RefCell::new(false);
         let state = context.state.lock().unwrap();
@@ -268,17 +493,7 @@ fn handle_create_node(
                     return false;
                 }
             };
-            let direction_to_compare = match node.direction() {
-                Ok(value) => value,
-                Err(value) => {
-                    control_flow.replace(true);
-                    context.server_channel
-                        .send(&context.request, MessageResponse::Error(value))
-                        .unwrap();
-                    return false;
-                }
-            };
-            name_to_compare == name && direction_to_compare == direction
+            name_to_compare == name && node.direction() == Some(direction.clone())
         });
         if is_exists {
             context.server_channel
@@ -289,42 +504,38 @@ fn handle_create_node(
                     }
                 ))
                 .unwrap();
+            return;
+        }
+        if *control_flow.borrow() == true {
+            return;
         }
     }
-    let default_audio_position = format!(
-        "[ {} ]",
-        (1..=channels + 1)
-            .map(|n| n.to_string())
-            .collect::<Vec<_>>()
-            .join(" ")
-    );
-    let properties = &pipewire::properties::properties! {
-        *pipewire::keys::FACTORY_NAME => "support.null-audio-sink",
-        *pipewire::keys::NODE_NAME => name.clone(),
-        *pipewire::keys::NODE_DESCRIPTION => description.clone(),
-        *pipewire::keys::NODE_NICK => nickname.clone(),
+    let audio_position = options.position.to_string();
+    let mut properties = pipewire::properties::properties! {
+        *pipewire::keys::FACTORY_NAME => options.factory_name.clone(),
+        *pipewire::keys::NODE_NAME => options.name.clone(),
+        *pipewire::keys::NODE_DESCRIPTION => options.description.clone(),
+        *pipewire::keys::NODE_NICK => options.nickname.clone(),
         *pipewire::keys::MEDIA_CLASS => match direction {
             Direction::Input => MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SOURCE,
             Direction::Output => MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SINK,
         },
-        *pipewire::keys::OBJECT_LINGER => "false",
-        *pipewire::keys::AUDIO_CHANNELS => channels.to_string(),
-        MONITOR_CHANNEL_VOLUMES_PROPERTY_KEY => "true",
-        MONITOR_PASSTHROUGH_PROPERTY_KEY => "true",
-        AUDIO_POSITION_PROPERTY_KEY => match channels {
-            1 => "[ MONO ]",
-            2 => "[ FL FR ]", // 2.0
-            3 => "[ FL FR LFE ]", // 2.1
-            4 => "[ FL FR RL RR ]", // 4.0
-            5 => "[ FL FR FC RL RR ]", // 5.0
-            6 => "[ FL FR FC RL RR LFE ]", // 5.1
-            7 => "[ FL FR FC RL RR SL SR ]", // 7.0
-            8 => "[ FL FR FC RL RR SL SR LFE ]", // 7.1
-            _ => default_audio_position.as_str(),
-        }
+        *pipewire::keys::OBJECT_LINGER => options.linger.to_string(),
+        *pipewire::keys::AUDIO_CHANNELS => options.channels().to_string(),
+        AUDIO_POSITION_PROPERTY_KEY => audio_position,
     };
+    if let Some(sample_rate) = options.sample_rate {
+        properties.insert(*pipewire::keys::AUDIO_RATE, sample_rate.to_string());
+    }
+    if let Some(sample_format) = options.sample_format {
+        properties.insert(*pipewire::keys::AUDIO_FORMAT, sample_format.spa_name());
+    }
+    for (key, value) in options.properties.iter() {
+        properties.insert(key.clone(), value.clone());
+    }
+    let properties = &properties;
     let node: pipewire::node::Node = match context.core
-        .create_object("adapter", properties)
+        .create_object(FACTORY_NAME_PROPERTY_VALUE_ADAPTER, properties)
         .map_err(move |error| {
             Error {
                 description: error.to_string(),
@@ -338,53 +549,13 @@ fn handle_create_node(
             return;
         }
     };
-    let core_sync = context.core_sync.clone();
     let listener_server_channel = context.server_channel.clone();
     let listener_state = context.state.clone();
     let listener_properties = properties.clone();
-    core_sync.register(
-        PIPEWIRE_CORE_SYNC_CREATE_DEVICE_SEQ,
-        move |control_flow| {
-            let mut state = listener_state.lock().unwrap();
-            let mut nodes = match state.get_nodes_mut() {
-                Ok(value) => value,
-                Err(value) => {
-                    listener_server_channel
-                        .send(&context.request, MessageResponse::Error(value))
-                        .unwrap();
-                    control_flow.release();
-                    return;
-                }
-            };
-            let node = nodes.iter_mut()
-                .find(move |(_, node)| {
-                    node.state() == GlobalObjectState::Pending
-                });
-            match node {
-                Some((id, node)) => {
-                    let properties = dict_ref_to_hashmap(listener_properties.dict());
-                    node.set_properties(properties);
-                    listener_server_channel
-                        .send(
-                            &context.request,
-                            MessageResponse::CreateNode((*id).clone())
-                        )
-                        .unwrap();
-                }
-                None => {
-                    listener_server_channel
-                        .send(
-                            &context.request,
-                            MessageResponse::Error(Error {
-                                description: "Created node not found".to_string(),
-                            })
-                        )
-                        .unwrap();
-                }
-            }
-            control_flow.release();
-        }
-    );
+    // Request is answered either once node is bound or when server rejects it
+    let request = Rc::new(context.request);
+    let error_server_channel = context.server_channel.clone();
+    let error_request = request.clone();
     let mut state = context.state.lock().unwrap();
     // We need to store created node object as orphan since it had not been
     // registered by server at this point (does not have an id yet).
@@ -397,7 +568,35 @@ fn handle_create_node(
     // as a NodeState.
     // OrphanState object define "removed" listener from Proxy to ensure our orphan
     // proxy object is removed when proper NodeState object is retrieved from server
-    let orphan = OrphanState::new(node.upcast());
+    //
+    // Created node is identified by the id its proxy is bound to, other nodes could be
+    // registered in between (e.g. concurrent creations or hotplugged devices).
+    let mut orphan = OrphanState::new(node.upcast());
+    orphan.add_bound_listener(
+        move |control_flow, id| {
+            let mut state = listener_state.lock().unwrap();
+            let properties = dict_ref_to_hashmap(listener_properties.dict());
+            state.set_created_node_properties(id.clone(), properties);
+            state.notify_waiters();
+            listener_server_channel
+                .send(&request, MessageResponse::CreateNode(id))
+                .unwrap();
+            control_flow.release();
+        }
+    );
+    orphan.add_error_listener(
+        move |control_flow, message| {
+            error_server_channel
+                .send(
+                    &error_request,
+                    MessageResponse::Error(Error {
+                        description: format!("Failed to create node: {}", message),
+                    })
+                )
+                .unwrap();
+            control_flow.release();
+        }
+    );
     state.insert_orphan(orphan);
 }
 fn handle_delete_node(
@@ -426,10 +625,6 @@ fn handle_enumerate_node(
 {
     let state = context.state.lock().unwrap();
     let default_audio_nodes = state.get_default_audio_nodes();
-    let default_audio_node = match direction {
-        Direction::Input => default_audio_nodes.source.clone(),
-        Direction::Output => default_audio_nodes.sink.clone()
-    };
     let filter_value = match direction {
         Direction::Input => MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SOURCE,
         Direction::Output => MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SINK,
@@ -443,46 +638,102 @@ fn handle_enumerate_node(
             return;
         }
     };
-    let nodes: Vec<NodeInfo> = nodes
+    let nodes: Result<Vec<NodeInfo>, Error> = nodes
         .iter()
-        .filter_map(|(id, node)| {
+        .filter(|(_, node)| {
             let properties = node.properties().unwrap();
-            let format = node.format().unwrap();
-            if properties.iter().any(|(_, v)| v == filter_value) {
-                Some((id, properties, format))
-            } else {
-                None
-            }
-        })
-        .map(|(id, properties, format)| {
-            let name = properties.get(*pipewire::keys::NODE_NAME).unwrap().clone();
-            let description = properties
-                .get(*pipewire::keys::NODE_DESCRIPTION)
-                .unwrap()
-                .clone();
-            let nickname = match properties.contains_key(*pipewire::keys::NODE_NICK) {
-                true => properties.get(*pipewire::keys::NODE_NICK).unwrap().clone(),
-                false => name.clone(),
-            };
-            let is_default = name == default_audio_node;
-            NodeInfo {
-                id: (*id).clone().into(),
-                name,
-                description,
-                nickname,
-                direction: direction.clone(),
-                is_default,
-                format: format.clone()
-            }
+            properties.iter().any(|(_, v)| v == filter_value)
         })
+        .map(|(id, node)| map_node_info(id, node, &default_audio_nodes))
         .collect();
-    context.server_channel.send(&context.request, MessageResponse::EnumerateNodes(nodes)).unwrap();
+    let response = match nodes {
+        Ok(value) => MessageResponse::EnumerateNodes(value),
+        Err(value) => MessageResponse::Error(value)
+    };
+    context.server_channel.send(&context.request, response).unwrap();
+}
+fn handle_update_node_properties(
+    context: Context,
+    id: GlobalId,
+    properties: HashMap<String, String>,
+)
+{
+    let mut state = context.state.lock().unwrap();
+    let node = match state.get_node(&id) {
+        Ok(value) => value,
+        Err(value) => {
+            context.server_channel
+                .send(&context.request, MessageResponse::Error(value))
+                .unwrap();
+            return;
+        }
+    };
+    if let Err(value) = node.update_properties(&properties) {
+        context.server_channel
+            .send(&context.request, MessageResponse::Error(value))
+            .unwrap();
+        return;
+    }
+    let server_channel = context.server_channel.clone();
+    let request = context.request;
+    state.add_waiter(
+        StateCondition::NodeProperties(id, properties),
+        move |result| {
+            let response = match result {
+                Ok(_) => MessageResponse::UpdateNodeProperties,
+                Err(value) => MessageResponse::Error(value),
+            };
+            server_channel
+                .send(&request, response)
+                .unwrap();
+        }
+    );
+}
+fn handle_send_node_command(
+    context: Context,
+    id: GlobalId,
+    command: u32,
+    suspended: bool,
+    response: MessageResponse,
+)
+{
+    let mut state = context.state.lock().unwrap();
+    let node = match state.get_node(&id) {
+        Ok(value) => value,
+        Err(value) => {
+            context.server_channel
+                .send(&context.request, MessageResponse::Error(value))
+                .unwrap();
+            return;
+        }
+    };
+    if let Err(value) = node.send_command(&context.core, command) {
+        context.server_channel
+            .send(&context.request, MessageResponse::Error(value))
+            .unwrap();
+        return;
+    }
+    // Answered once server reported node status
+    let server_channel = context.server_channel.clone();
+    let request = context.request;
+    state.add_waiter(
+        StateCondition::NodeSuspended(id, suspended),
+        move |result| {
+            let response = match result {
+                Ok(_) => response,
+                Err(value) => MessageResponse::Error(value),
+            };
+            server_channel
+                .send(&request, response)
+                .unwrap();
+        }
+    );
 }
 fn handle_create_stream(
     context: Context,
     node_id: GlobalId,
     direction: Direction,
-    format: AudioStreamInfo,
+    format: StreamInfo,
     callback: StreamCallback,
 ) 
 {
@@ -506,20 +757,51 @@ fn handle_create_stream(
             return;
         }
     };
+    let (stream_name_prefix, media_type, media_class) = match (&format, &direction) {
+        (StreamInfo::Audio(_), Direction::Input) => (
+            "stream",
+            MEDIA_TYPE_PROPERTY_VALUE_AUDIO,
+            MEDIA_CLASS_PROPERTY_VALUE_STREAM_INPUT_AUDIO
+        ),
+        (StreamInfo::Audio(_), Direction::Output) => (
+            "stream",
+            MEDIA_TYPE_PROPERTY_VALUE_AUDIO,
+            MEDIA_CLASS_PROPERTY_VALUE_STREAM_OUTPUT_AUDIO
+        ),
+        (StreamInfo::Video(_), Direction::Input) => (
+            "video_stream",
+            MEDIA_TYPE_PROPERTY_VALUE_VIDEO,
+            MEDIA_CLASS_PROPERTY_VALUE_STREAM_INPUT_VIDEO
+        ),
+        (StreamInfo::Video(_), Direction::Output) => (
+            "video_stream",
+            MEDIA_TYPE_PROPERTY_VALUE_VIDEO,
+            MEDIA_CLASS_PROPERTY_VALUE_STREAM_OUTPUT_VIDEO
+        ),
+        (StreamInfo::Midi(_), Direction::Input) => (
+            "midi_stream",
+            MEDIA_TYPE_PROPERTY_VALUE_MIDI,
+            MEDIA_CLASS_PROPERTY_VALUE_STREAM_INPUT_MIDI
+        ),
+        (StreamInfo::Midi(_), Direction::Output) => (
+            "midi_stream",
+            MEDIA_TYPE_PROPERTY_VALUE_MIDI,
+            MEDIA_CLASS_PROPERTY_VALUE_STREAM_OUTPUT_MIDI
+        ),
+    };
     let stream_name = match direction {
         Direction::Input => {
-            format!("{}.stream_input", node_name)
+            format!("{}.{}_input", node_name, stream_name_prefix)
         }
         Direction::Output => {
-            format!("{}.stream_output", node_name)
+            format!("{}.{}_output", node_name, stream_name_prefix)
         }
     };
+    // Session manager links stream to the requested node instead of the default one
     let properties = pipewire::properties::properties! {
-        *pipewire::keys::MEDIA_TYPE => MEDIA_TYPE_PROPERTY_VALUE_AUDIO,
-        *pipewire::keys::MEDIA_CLASS => match direction {
-            Direction::Input => MEDIA_CLASS_PROPERTY_VALUE_STREAM_INPUT_AUDIO,
-            Direction::Output => MEDIA_CLASS_PROPERTY_VALUE_STREAM_OUTPUT_AUDIO,
-        },
+        *pipewire::keys::MEDIA_TYPE => media_type,
+        *pipewire::keys::MEDIA_CLASS => media_class,
+        TARGET_OBJECT_PROPERTY_KEY => node_name.clone(),
     };
     let stream = match pipewire::stream::Stream::new(
         &context.core,
@@ -541,10 +823,32 @@ fn handle_create_stream(
     };
     let mut stream = StreamState::new(
         stream_name.clone(),
-        format.into(),
+        format,
         direction.into(),
         stream
     );
+    let event_sender = context.event_sender.clone();
+    let format_stream_name = stream_name.clone();
+    let server_channel = context.server_channel.clone();
+    stream.add_format_listener(
+        move |_, format| {
+            match format {
+                Ok(value) => {
+                    event_sender
+                        .send(EventMessage::SetStreamFormat {
+                            name: format_stream_name.clone(),
+                            format: value,
+                        })
+                        .unwrap();
+                }
+                Err(value) => {
+                    server_channel
+                        .fire(MessageResponse::Error(value))
+                        .unwrap();
+                }
+            };
+        }
+    );
     stream.add_process_listener(callback);
     if let Err(value) = state.insert_stream(stream_name.clone(), stream) {
         context.server_channel
@@ -588,6 +892,8 @@ fn handle_delete_stream(
             .unwrap();
         return;
     };
+    // Format waiters of deleted stream fail right away
+    state.notify_waiters();
     context.server_channel.send(&context.request, MessageResponse::DeleteStream).unwrap();
 }
 fn handle_connect_stream(
@@ -636,6 +942,39 @@ fn handle_disconnect_stream(
     };
     context.server_channel.send(&context.request, MessageResponse::DisconnectStream).unwrap();
 }
+fn handle_get_stream_format(
+    context: Context,
+    name: String,
+)
+{
+    let mut state = context.state.lock().unwrap();
+    let negotiated_format = match state.get_stream(&name) {
+        Ok(value) => value.negotiated_format(),
+        Err(value) => {
+            context.server_channel
+                .send(&context.request, MessageResponse::Error(value))
+                .unwrap();
+            return;
+        }
+    };
+    let server_channel = context.server_channel.clone();
+    let request = context.request;
+    state.add_waiter(
+        StateCondition::StreamFormat(name),
+        move |result| {
+            let response = match (result, negotiated_format.borrow().clone()) {
+                (Ok(_), Some(value)) => MessageResponse::GetStreamFormat(value),
+                (Ok(_), None) => MessageResponse::Error(Error {
+                    description: "Stream format is not negotiated".to_string(),
+                }),
+                (Err(value), _) => MessageResponse::Error(value),
+            };
+            server_channel
+                .send(&request, response)
+                .unwrap();
+        }
+    );
+}
 fn handle_check_session_manager_registered(
     context: Context,
 ) 
@@ -699,65 +1038,47 @@ fn handle_check_session_manager_registered(
         )
         .unwrap();
 }
-fn handle_settings_state(
-    context: Context,
-)
-{
-    let state = context.state.lock().unwrap();
-    context.server_channel
-        .send(&context.request, MessageResponse::SettingsState(state.get_settings().state))
-        .unwrap();
-}
-fn handle_default_audio_nodes_state(
+fn handle_wait_node_initialized(
     context: Context,
+    id: GlobalId,
 )
 {
-    let state = context.state.lock().unwrap();
-    context.server_channel
-        .send(&context.request, MessageResponse::DefaultAudioNodesState(state.get_default_audio_nodes().state))
-        .unwrap();
-}
-fn handle_node_state(
-    context: Context,
-    id: GlobalId,
-) {
-    let state = context.state.lock().unwrap();
-    let node = match state.get_node(&id) {
-        Ok(value) => value,
-        Err(value) => {
-            context.server_channel
-                .send(&context.request, MessageResponse::Error(value))
+    let mut state = context.state.lock().unwrap();
+    let server_channel = context.server_channel.clone();
+    let request = context.request;
+    let response_id = id.clone();
+    state.add_waiter(
+        StateCondition::NodeInitialized(id),
+        move |result| {
+            let response = match result {
+                Ok(_) => MessageResponse::NodeInitialized(response_id),
+                Err(value) => MessageResponse::Error(value),
+            };
+            server_channel
+                .send(&request, response)
                 .unwrap();
-            return;
         }
-    };
-    let state = node.state();
-    context.server_channel
-        .send(&context.request, MessageResponse::NodeState(state))
-        .unwrap();
+    );
 }
-fn handle_node_states(
+fn handle_wait_ready(
     context: Context,
-) 
+)
 {
-    let state = context.state.lock().unwrap();
-    let nodes = match state.get_nodes() {
-        Ok(value) => value,
-        Err(value) => {
-            context.server_channel
-                .send(&context.request, MessageResponse::Error(value))
+    let mut state = context.state.lock().unwrap();
+    let server_channel = context.server_channel.clone();
+    let request = context.request;
+    state.add_waiter(
+        StateCondition::Ready,
+        move |result| {
+            let response = match result {
+                Ok(_) => MessageResponse::Ready,
+                Err(value) => MessageResponse::Error(value),
+            };
+            server_channel
+                .send(&request, response)
                 .unwrap();
-            return;
         }
-    };
-    let states = nodes.iter()
-        .map(move |(_, node)| {
-            node.state()
-        })
-        .collect::<Vec<_>>();
-    context.server_channel
-        .send(&context.request, MessageResponse::NodeStates(states))
-        .unwrap();
+    );
 }
 fn handle_node_count(
     context: Context,
@@ -777,6 +1098,17 @@ fn handle_node_count(
         }
     };
 }
+fn handle_diagnostics(
+    context: Context,
+)
+{
+    let state = context.state.lock().unwrap();
+    let mut diagnostics = state.diagnostics();
+    diagnostics.listeners += context.core_sync.get_listener_names().len();
+    context.server_channel
+        .send(&context.request, MessageResponse::Diagnostics(diagnostics))
+        .unwrap();
+}
 #[cfg(test)]
 fn handle_listeners(
     context: Context,
@@ -806,6 +1138,7 @@ fn handle_listeners(
             ((*name).clone(), stream.get_listener_names())
         })
         .collect::<HashMap<_, _>>();
+    let orphans = state.get_orphans_listener_names();
     context.server_channel
         .send(
             &context.request,
@@ -814,7 +1147,39 @@ fn handle_listeners(
                 metadata,
                 nodes,
                 streams,
+                orphans,
             }
         )
         .unwrap();
-}
\ No newline at end of file
+}
+#[cfg(test)]
+fn handle_provoke_core_error(
+    context: Context,
+)
+{
+    let properties = pipewire::properties::Properties::new();
+    // Proxy is dropped right away, server error is reported on core anyway
+    let _ = context.core.create_object::<pipewire::node::Node>("not-existing-factory", &properties);
+    context.server_channel
+        .send(&context.request, MessageResponse::ProvokeCoreError)
+        .unwrap();
+}
+#[cfg(test)]
+fn handle_provoke_node_format_error(
+    context: Context,
+    id: GlobalId,
+)
+{
+    // Same event format listener sends when EnumFormat deserialization fails
+    context.event_sender
+        .send(EventMessage::SetNodeFormatError {
+            id,
+            error: Error {
+                description: "Provoked node format error".to_string(),
+            },
+        })
+        .unwrap();
+    context.server_channel
+        .send(&context.request, MessageResponse::ProvokeNodeFormatError)
+        .unwrap();
+}
@@ -2,15 +2,18 @@ use crate::client::connection_string::PipewireClientInfo;
 use crate::client::handlers::event::event_handler;
 use crate::client::handlers::registry::registry_global_handler;
 use crate::client::handlers::request::request_handler;
-use crate::constants::{PIPEWIRE_CORE_SYNC_INITIALIZATION_SEQ, PIPEWIRE_RUNTIME_DIR_ENVIRONMENT_KEY};
 use crate::error::Error;
 use crate::messages::{EventMessage, MessageRequest, MessageResponse};
 use crate::states::GlobalState;
 use std::cell::RefCell;
 use std::rc::Rc;
-use std::sync::{Arc, Mutex, Once};
+use std::sync::{mpsc, Arc, Mutex, Once};
+use std::thread;
+use std::thread::ThreadId;
+use std::time::Duration;
 use libc::atexit;
-use crate::client::channel::ServerChannel;
+use pipewire::loop_::IsLoopRc;
+use crate::client::channel::{Request, ServerChannel};
 use crate::listeners::PipewireCoreSync;
 
 static AT_EXIT: Once = Once::new();
@@ -19,12 +22,116 @@ extern "C" fn at_exit_callback() {
     unsafe { pipewire::deinit(); }
 }
 
-pub fn pw_thread(
-    client_info: PipewireClientInfo,
-    mut server_channel: ServerChannel<MessageRequest, MessageResponse>,
-    event_sender: pipewire::channel::Sender<EventMessage>,
-    event_receiver: pipewire::channel::Receiver<EventMessage>,
-) {
+/// Handlers and PipeWire objects bound to a loop, they are detached once dropped.
+/// Fields are declared in drop order: listeners go before the core and context they belong to.
+pub struct PipewireLoopAttachment<'l> {
+    _registry_listener: pipewire::registry::Listener,
+    _attached_pw_receiver: pipewire::channel::AttachedReceiver<'l, Request<MessageRequest>>,
+    _attached_event_receiver: pipewire::channel::AttachedReceiver<'l, EventMessage>,
+    _core_listener: pipewire::core::Listener,
+    _core_sync: Rc<PipewireCoreSync>,
+    _state: Arc<Mutex<GlobalState>>,
+    _registry: Rc<pipewire::registry::Registry>,
+    _core: Rc<pipewire::core::Core>,
+    _context: pipewire::context::Context,
+}
+
+/// `ThreadLoop` owned by a client, attached handlers are only touched while loop lock is held.
+pub(crate) struct PipewireThreadLoop {
+    // Borrows boxed thread loop, always dropped first
+    attachment: Option<PipewireLoopAttachment<'static>>,
+    thread_loop: Option<Box<pipewire::thread_loop::ThreadLoop>>,
+    thread_id: ThreadId,
+}
+
+// Thread loop and attached objects are only used under loop lock, whatever the calling thread
+unsafe impl Send for PipewireThreadLoop {}
+
+impl PipewireThreadLoop {
+    pub(crate) fn start(
+        client_info: PipewireClientInfo,
+        server_channel: &mut ServerChannel<MessageRequest, MessageResponse>,
+        event_sender: pipewire::channel::Sender<EventMessage>,
+        event_receiver: pipewire::channel::Receiver<EventMessage>,
+        timeout: Duration,
+    ) -> Result<Self, Error> {
+        init();
+
+        let thread_loop = match unsafe { pipewire::thread_loop::ThreadLoop::new(Some(client_info.name.as_str()), None) } {
+            Ok(value) => Box::new(value),
+            Err(value) => return Err(Error {
+                description: format!("Failed to create PipeWire thread loop: {}", value),
+            }),
+        };
+        // Boxed loop keeps its address once moved, attachment never outlives it
+        let loop_: &'static pipewire::thread_loop::ThreadLoop = unsafe {
+            &*(thread_loop.as_ref() as *const pipewire::thread_loop::ThreadLoop)
+        };
+
+        let lock = thread_loop.lock();
+        let attachment = attach(
+            loop_,
+            client_info,
+            server_channel,
+            event_sender,
+            event_receiver,
+            || {}
+        )?;
+        let (thread_id_sender, thread_id_receiver) = mpsc::channel();
+        let thread_id_source = thread_loop.loop_().add_event(move || {
+            let _ = thread_id_sender.send(thread::current().id());
+        });
+        thread_id_source.signal();
+        drop(lock);
+
+        thread_loop.start();
+        let thread_id = thread_id_receiver.recv_timeout(timeout);
+        let lock = thread_loop.lock();
+        drop(thread_id_source);
+        drop(lock);
+
+        let mut thread_loop = PipewireThreadLoop {
+            attachment: Some(attachment),
+            thread_loop: Some(thread_loop),
+            thread_id: thread::current().id(),
+        };
+        match thread_id {
+            Ok(value) => {
+                thread_loop.thread_id = value;
+                Ok(thread_loop)
+            }
+            // Loop is stopped once dropped from current thread
+            Err(_) => Err(Error {
+                description: "PipeWire thread loop did not start".to_string(),
+            }),
+        }
+    }
+}
+
+impl Drop for PipewireThreadLoop {
+    fn drop(&mut self) {
+        let Some(thread_loop) = self.thread_loop.take() else {
+            return;
+        };
+        let lock = thread_loop.lock();
+        self.attachment.take();
+        drop(lock);
+        if thread::current().id() != self.thread_id {
+            thread_loop.stop();
+            return;
+        }
+        // Dropped from a loop callback (e.g. a stream callback owning last client handle),
+        // loop can't stop itself. It is stopped from another thread once callback returns.
+        let remaining = PipewireThreadLoop {
+            attachment: None,
+            thread_loop: Some(thread_loop),
+            thread_id: self.thread_id,
+        };
+        thread::spawn(move || drop(remaining));
+    }
+}
+
+fn init() {
     pipewire::init();
 
     AT_EXIT.call_once(|| {
@@ -32,51 +139,41 @@ pub fn pw_thread(
             atexit(at_exit_callback);
         }
     });
-    
+}
+
+pub(crate) fn attach<'l, L: IsLoopRc>(
+    loop_: &'l L,
+    client_info: PipewireClientInfo,
+    server_channel: &mut ServerChannel<MessageRequest, MessageResponse>,
+    event_sender: pipewire::channel::Sender<EventMessage>,
+    event_receiver: pipewire::channel::Receiver<EventMessage>,
+    quit: impl Fn() + 'static,
+) -> Result<PipewireLoopAttachment<'l>, Error> {
+    init();
+
+    // Absolute remote name is used as is, environment is not looked up by libpipewire.
+    // Several clients could then target different servers from the same process.
     let connection_properties = Some(pipewire::properties::properties! {
-        PIPEWIRE_RUNTIME_DIR_ENVIRONMENT_KEY => client_info.socket_location,
-        *pipewire::keys::REMOTE_NAME => client_info.socket_name,
+        *pipewire::keys::REMOTE_NAME => client_info.socket_path.to_string_lossy().to_string(),
         *pipewire::keys::APP_NAME => client_info.name,
     });
 
-    let main_loop = match pipewire::main_loop::MainLoop::new(None) {
+    let context = match pipewire::context::Context::new(loop_) {
         Ok(value) => value,
-        Err(value) => {
-            server_channel
-                .fire(MessageResponse::Error(Error {
-                    description: format!("Failed to create PipeWire main loop: {}", value),
-                }))
-                .unwrap();
-            return;
-        }
+        Err(value) => return Err(Error {
+            description: format!("Failed to create PipeWire context: {}", value),
+        }),
     };
 
-    let context = match pipewire::context::Context::new(&main_loop) {
-        Ok(value) => Rc::new(value),
-        Err(value) => {
-            server_channel
-                .fire(MessageResponse::Error(Error {
-                    description: format!("Failed to create PipeWire context: {}", value),
-                }))
-                .unwrap();
-            return;
-        }
-    };
-    
     let core = match context.connect(connection_properties.clone()) {
         Ok(value) => value,
-        Err(value) => {
-            server_channel
-                .fire(MessageResponse::Error(Error {
-                    description: format!("Failed to connect PipeWire server: {}", value),
-                }))
-                .unwrap();
-            return;
-        }
+        Err(value) => return Err(Error {
+            description: format!("Failed to connect PipeWire server: {}", value),
+        }),
     };
 
     let listener_main_sender = server_channel.clone();
-    let _core_listener = core
+    let core_listener = core
         .add_listener_local()
         .error(move |_, _, _, message| {
             listener_main_sender
@@ -89,14 +186,9 @@ pub fn pw_thread(
 
     let registry = match core.get_registry() {
         Ok(value) => Rc::new(value),
-        Err(value) => {
-            server_channel
-                .fire(MessageResponse::Error(Error {
-                    description: format!("Failed to get Pipewire registry: {}", value),
-                }))
-                .unwrap();
-            return;
-        }
+        Err(value) => return Err(Error {
+            description: format!("Failed to get Pipewire registry: {}", value),
+        }),
     };
 
     let core_sync = Rc::new(PipewireCoreSync::new(Rc::new(RefCell::new(core.clone()))));
@@ -105,7 +197,6 @@ pub fn pw_thread(
 
     let listener_main_sender = server_channel.clone();
     core_sync.register(
-        PIPEWIRE_CORE_SYNC_INITIALIZATION_SEQ,
         move |control_flow| {
             listener_main_sender
                 .fire(MessageResponse::Initialized)
@@ -114,8 +205,8 @@ pub fn pw_thread(
         }
     );
 
-    let _attached_event_receiver = event_receiver.attach(
-        main_loop.loop_(),
+    let attached_event_receiver = event_receiver.attach(
+        loop_.as_ref(),
         event_handler(
             state.clone(),
             server_channel.clone(),
@@ -123,18 +214,20 @@ pub fn pw_thread(
         )
     );
 
-    let _attached_pw_receiver = server_channel.attach(
-        main_loop.loop_(),
+    let attached_pw_receiver = server_channel.attach(
+        loop_.as_ref(),
         request_handler(
             core.clone(),
             core_sync.clone(),
-            main_loop.clone(),
+            quit,
             state.clone(),
-            server_channel.clone()
+            server_channel.clone(),
+            event_sender.clone()
         )
     );
 
-    let _registry_listener = registry
+    let listener_state = state.clone();
+    let registry_listener = registry
         .add_listener_local()
         .global(registry_global_handler(
             state.clone(),
@@ -143,10 +236,62 @@ pub fn pw_thread(
             event_sender.clone(),
         ))
         .global_remove(move |global_id| {
-            let mut state = state.lock().unwrap();
-            state.remove(&global_id.into())
+            let mut state = listener_state.lock().unwrap();
+            state.remove(&global_id.into());
+            state.notify_waiters();
         })
         .register();
 
+    Ok(PipewireLoopAttachment {
+        _registry_listener: registry_listener,
+        _attached_pw_receiver: attached_pw_receiver,
+        _attached_event_receiver: attached_event_receiver,
+        _core_listener: core_listener,
+        _core_sync: core_sync,
+        _state: state,
+        _registry: registry,
+        _core: core,
+        _context: context,
+    })
+}
+
+pub fn pw_thread(
+    client_info: PipewireClientInfo,
+    mut server_channel: ServerChannel<MessageRequest, MessageResponse>,
+    event_sender: pipewire::channel::Sender<EventMessage>,
+    event_receiver: pipewire::channel::Receiver<EventMessage>,
+) {
+    init();
+
+    let main_loop = match pipewire::main_loop::MainLoop::new(None) {
+        Ok(value) => value,
+        Err(value) => {
+            server_channel
+                .fire(MessageResponse::Error(Error {
+                    description: format!("Failed to create PipeWire main loop: {}", value),
+                }))
+                .unwrap();
+            return;
+        }
+    };
+
+    let quit_main_loop = main_loop.clone();
+    let _attachment = match attach(
+        &main_loop,
+        client_info,
+        &mut server_channel,
+        event_sender,
+        event_receiver,
+        move || quit_main_loop.quit()
+    ) {
+        Ok(value) => value,
+        Err(value) => {
+            server_channel
+                .fire(MessageResponse::Error(value))
+                .unwrap();
+            return;
+        }
+    };
+
     main_loop.run();
-}
\ No newline at end of file
+}
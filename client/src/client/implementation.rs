@@ -2,29 +2,36 @@ extern crate pipewire;
 
 use std::thread;
 use crate::client::api::{CoreApi, InternalApi, NodeApi, StreamApi};
-use crate::client::channel::channels;
+use crate::client::channel::{channels, ClientChannel};
 use crate::client::connection_string::{PipewireClientInfo, PipewireClientSocketPath};
-use crate::client::handlers::thread;
+use crate::client::handlers::{attach, thread, PipewireLoopAttachment, PipewireThreadLoop};
 use crate::error::Error;
+use pipewire::loop_::IsLoopRc;
+use crate::{CallOptions, RemoteOptions, ShutdownOptions, ShutdownReport};
 use crate::messages::{EventMessage, MessageRequest, MessageResponse};
-use crate::states::GlobalObjectState;
-use crate::utils::Backoff;
 use std::fmt::{Debug, Formatter};
 use std::path::PathBuf;
 use std::string::ToString;
 use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
 
 pub(super) static CLIENT_NAME_PREFIX: &str = "pipewire-client";
 pub(super) static CLIENT_INDEX: AtomicU32 = AtomicU32::new(0);
 
-pub struct PipewireClient {
-    pub(crate) name: String,
+enum PipewireClientBackend {
+    /// Dedicated thread running its own main loop
+    Thread(JoinHandle<()>),
+    ThreadLoop(PipewireThreadLoop),
+}
+
+struct PipewireClientInner {
+    name: String,
     socket_path: PathBuf,
-    thread_handle: Option<JoinHandle<()>>,
+    // Unset when attached to a loop owned by the application
+    backend: Mutex<Option<PipewireClientBackend>>,
     timeout: Duration,
     internal_api: Arc<InternalApi>,
     core_api: CoreApi,
@@ -32,22 +39,34 @@ pub struct PipewireClient {
     stream_api: StreamApi,
 }
 
+/// Handles share the same connection, requests could be sent concurrently from any thread.
+/// PipeWire thread is stopped once the last handle is dropped.
+///
+/// A handle moved into a stream callback is owned by client state, client then keeps itself
+/// alive: PipeWire thread is only stopped by [`PipewireClient::shutdown`], which releases
+/// streams and their callbacks.
+#[derive(Clone)]
+pub struct PipewireClient {
+    inner: Arc<PipewireClientInner>,
+}
+
 impl PipewireClient {
+    /// Connect to the remote defined by process environment
     pub fn new(
         runtime: Arc<Runtime>,
         timeout: Duration,
-    ) -> Result<Self, Error> {        
-        let name = format!("{}-{}", CLIENT_NAME_PREFIX, CLIENT_INDEX.load(Ordering::SeqCst));
-        CLIENT_INDEX.fetch_add(1, Ordering::SeqCst);
+    ) -> Result<Self, Error> {
+        Self::connect(runtime, timeout, RemoteOptions::default())
+    }
 
-        let socket_path = PipewireClientSocketPath::from_env();
+    /// Connect to given remote, clients targeting different servers could live side by side
+    pub fn connect(
+        runtime: Arc<Runtime>,
+        timeout: Duration,
+        remote: RemoteOptions,
+    ) -> Result<Self, Error> {
+        let (name, socket_path, client_info) = Self::client_info(remote)?;
 
-        let client_info = PipewireClientInfo {
-            name: name.clone(),
-            socket_location: socket_path.parent().unwrap().to_str().unwrap().to_string(),
-            socket_name: socket_path.file_name().unwrap().to_str().unwrap().to_string(),
-        };
-        
         let (client_channel, server_channel) = channels(runtime.clone());
         let (event_sender, event_receiver) = pipewire::channel::channel::<EventMessage>();
 
@@ -58,68 +77,187 @@ impl PipewireClient {
             event_receiver
         ));
 
+        let client = Self::from_channel(
+            name,
+            socket_path,
+            Some(PipewireClientBackend::Thread(pw_thread)),
+            client_channel,
+            timeout
+        );
+        client.wait_ready()?;
+        Ok(client)
+    }
+
+    /// Connect to given remote through a `ThreadLoop` owned by the client, its lock is taken
+    /// internally. Loop is stopped with the client.
+    pub fn with_thread_loop(
+        runtime: Arc<Runtime>,
+        timeout: Duration,
+        remote: RemoteOptions,
+    ) -> Result<Self, Error> {
+        let (name, socket_path, client_info) = Self::client_info(remote)?;
+
+        let (client_channel, mut server_channel) = channels(runtime.clone());
+        let (event_sender, event_receiver) = pipewire::channel::channel::<EventMessage>();
+
+        let thread_loop = PipewireThreadLoop::start(
+            client_info,
+            &mut server_channel,
+            event_sender,
+            event_receiver,
+            timeout.clone()
+        )?;
+
+        let client = Self::from_channel(
+            name,
+            socket_path,
+            Some(PipewireClientBackend::ThreadLoop(thread_loop)),
+            client_channel,
+            timeout
+        );
+        client.wait_ready()?;
+        Ok(client)
+    }
+
+    /// Attach client handlers to an existing loop (e.g. application main loop or a started
+    /// `ThreadLoop`) instead of spawning a dedicated thread. Handlers are detached when the
+    /// returned attachment is dropped, it has to outlive every request.
+    ///
+    /// Requests are blocking and served by the loop, they must be sent from another thread than
+    /// the one running it. With a `ThreadLoop`, its lock has to be held while attaching and
+    /// dropping the attachment. Once the loop is running, [`PipewireClient::wait_ready`] has to
+    /// be called before any other request.
+    ///
+    /// Single-loop integration is not supported: there is no non-blocking request path, a request
+    /// sent from the thread running the loop (e.g. a GTK main thread) never gets its answer.
+    /// [`PipewireClient::with_thread_loop`] serves requests from its own loop thread instead.
+    pub fn attach<'l, L: IsLoopRc>(
+        loop_: &'l L,
+        runtime: Arc<Runtime>,
+        timeout: Duration,
+        remote: RemoteOptions,
+    ) -> Result<(Self, PipewireLoopAttachment<'l>), Error> {
+        let (name, socket_path, client_info) = Self::client_info(remote)?;
+
+        let (client_channel, mut server_channel) = channels(runtime.clone());
+        let (event_sender, event_receiver) = pipewire::channel::channel::<EventMessage>();
+
+        // Loop is owned by the application, Quit is never sent since there is no thread to join
+        let attachment = attach(
+            loop_,
+            client_info,
+            &mut server_channel,
+            event_sender,
+            event_receiver,
+            || {}
+        )?;
+
+        let client = Self::from_channel(name, socket_path, None, client_channel, timeout);
+        Ok((client, attachment))
+    }
+
+    fn client_info(remote: RemoteOptions) -> Result<(String, PathBuf, PipewireClientInfo), Error> {
+        let name = match remote.name {
+            Some(value) => value,
+            None => format!("{}-{}", CLIENT_NAME_PREFIX, CLIENT_INDEX.fetch_add(1, Ordering::SeqCst)),
+        };
+
+        // libpipewire resolves a relative remote name against runtime directories
+        let socket_path = match remote.socket_path {
+            Some(value) if value.is_absolute() => value,
+            Some(value) => return Err(Error {
+                description: format!("Socket path({}) is not absolute", value.display()),
+            }),
+            None => PipewireClientSocketPath::from_env()?,
+        };
+
+        let client_info = PipewireClientInfo {
+            name: name.clone(),
+            socket_path: socket_path.clone(),
+        };
+        Ok((name, socket_path, client_info))
+    }
+
+    fn from_channel(
+        name: String,
+        socket_path: PathBuf,
+        backend: Option<PipewireClientBackend>,
+        client_channel: ClientChannel<MessageRequest, MessageResponse>,
+        timeout: Duration,
+    ) -> Self {
         let internal_api = Arc::new(InternalApi::new(client_channel, timeout.clone()));
         let core_api = CoreApi::new(internal_api.clone());
         let node_api = NodeApi::new(internal_api.clone());
         let stream_api = StreamApi::new(internal_api.clone());
 
-        let client = Self {
-            name,
-            socket_path,
-            thread_handle: Some(pw_thread),
-            timeout,
-            internal_api,
-            core_api,
-            node_api,
-            stream_api,
-        };
+        Self {
+            inner: Arc::new(PipewireClientInner {
+                name,
+                socket_path,
+                backend: Mutex::new(backend),
+                timeout,
+                internal_api,
+                core_api,
+                node_api,
+                stream_api,
+            }),
+        }
+    }
 
-        match client.wait_initialization() {
+    /// Wait for server connection and session manager readiness, [`PipewireClient::new`] already
+    /// does it.
+    pub fn wait_ready(&self) -> Result<(), Error> {
+        // Every initialization step shares client timeout
+        let deadline = Instant::now() + self.inner.timeout;
+        match self.wait_initialization(deadline) {
             Ok(_) => {}
             Err(value) => return Err(Error {
                 description: format!("Initialization error: {}", value),
             })
         };
-        match client.wait_post_initialization() {
+        match self.wait_post_initialization(deadline) {
             Ok(_) => {}
-            Err(value) => {
-                let global_messages = &client.internal_api.channel.global_messages;
-                return Err(Error {
-                    description: format!("Post initialization error: {}", value),
-                })
-            },
+            Err(value) => return Err(Error {
+                description: format!("Post initialization error: {}", value),
+            }),
         };
-        Ok(client)
+        Ok(())
     }
 
-    fn wait_initialization(&self) -> Result<(), Error> {
-        let response = self.internal_api.wait_response_with_timeout(self.timeout);
-        let response = match response {
-            Ok(value) => value,
-            Err(value) => {
-                // Timeout is certainly due to missing session manager
-                // We need to check if that's the case. If session manager is running then we return
-                // timeout error.
-                return match self.core_api.check_session_manager_registered() {
-                    Ok(_) => Err(value),
-                    Err(value) => Err(value)
-                };
+    fn wait_initialization(&self, deadline: Instant) -> Result<(), Error> {
+        loop {
+            let response = self.inner.internal_api.wait_response_with_timeout(
+                deadline.saturating_duration_since(Instant::now())
+            );
+            let response = match response {
+                Ok(value) => value,
+                Err(value) => {
+                    // Timeout is certainly due to missing session manager
+                    // We need to check if that's the case. If session manager is running then we return
+                    // timeout error. Deadline is already over, check has its own timeout.
+                    return match self.inner.core_api.check_session_manager_registered() {
+                        Ok(_) => Err(value),
+                        Err(value) => Err(value)
+                    };
+                }
+            };
+            match response {
+                MessageResponse::Initialized => return Ok(()),
+                // Errors fired during initialization stay available through errors()
+                MessageResponse::Error(_) => continue,
+                _ => return Err(Error {
+                    description: format!("Received unexpected response: {:?}", response),
+                }),
             }
-        };
-        match response {
-            MessageResponse::Initialized => Ok(()),
-            _ => Err(Error {
-                description: format!("Received unexpected response: {:?}", response),
-            }),
         }
     }
 
-    fn wait_post_initialization(&self) -> Result<(), Error> {
-        let mut settings_initialized = false;
-        let mut default_audio_nodes_initialized = false;
-        let mut nodes_initialized = false;
-        self.core_api.check_session_manager_registered()?;
-        match self.node_api.count() {
+    fn wait_post_initialization(&self, deadline: Instant) -> Result<(), Error> {
+        let options = CallOptions::new();
+        self.inner.core_api
+            .with_options(options.until(deadline))
+            .check_session_manager_registered()?;
+        match self.inner.node_api.with_options(options.until(deadline)).count() {
             Ok(value) => {
                 if value == 0 {
                     return Err(Error {
@@ -129,77 +267,89 @@ impl PipewireClient {
             }
             Err(value) => return Err(value),
         }
-        let operation = move || {
-            if settings_initialized == false {
-                let settings_state = self.core_api.get_settings_state()?;
-                if settings_state == GlobalObjectState::Initialized {
-                    settings_initialized = true;
-                }
-            }
-            if default_audio_nodes_initialized == false {
-                let default_audio_nodes_state = self.core_api.get_default_audio_nodes_state()?;
-                if default_audio_nodes_state == GlobalObjectState::Initialized {
-                    default_audio_nodes_initialized = true;
-                }
-            }
-            if nodes_initialized == false {
-                let node_states = self.node_api.states()?;
-                let condition = node_states.iter()
-                    .all(|state| *state == GlobalObjectState::Initialized);
-                if condition {
-                    nodes_initialized = true;
-                }
-            }
-            if settings_initialized == false || default_audio_nodes_initialized == false || nodes_initialized == false {
-                return Err(Error {
-                    description: format!(
-                        r"Conditions not yet initialized:
-                            - settings: {}
-                            - default audio nodes: {}
-                            - nodes: {}",
-                        settings_initialized,
-                        default_audio_nodes_initialized,
-                        nodes_initialized
-                    ),
-                })
-            }
-            return Ok(());
-        };
-        let mut backoff = Backoff::constant(self.timeout.as_millis());
-        backoff.retry(operation)
+        self.inner.core_api
+            .with_options(options.until(deadline))
+            .wait_ready()
+    }
+
+    /// Application name the server knows this client by
+    pub fn name(&self) -> &str {
+        &self.inner.name
     }
 
     pub(crate) fn internal(&self) -> Arc<InternalApi> {
-        self.internal_api.clone()
+        self.inner.internal_api.clone()
+    }
+
+    /// Errors raised outside of a request (e.g. registry or metadata binding failures) since
+    /// last call. Only the most recent ones are kept.
+    pub fn errors(&self) -> Vec<Error> {
+        self.inner.internal_api.global_errors()
+    }
+
+    /// Release streams and created nodes (lingering ones are kept by default), then stop
+    /// PipeWire thread. Other handles of this client are disconnected, their pending requests fail.
+    pub fn shutdown(self, options: ShutdownOptions) -> Result<ShutdownReport, Error> {
+        let response = self.inner.internal_api.send_request(
+            &MessageRequest::Shutdown(options),
+            &CallOptions::default()
+        );
+        let report = match response {
+            Ok(MessageResponse::Shutdown(value)) => value,
+            Err(value) => return Err(value),
+            Ok(value) => return Err(Error {
+                description: format!("Received unexpected response: {:?}", value),
+            }),
+        };
+        self.inner.stop()?;
+        Ok(report)
     }
 
     pub fn core(&self) -> &CoreApi {
-        &self.core_api
+        &self.inner.core_api
     }
 
     pub fn node(&self) -> &NodeApi {
-        &self.node_api
+        &self.inner.node_api
     }
 
     pub fn stream(&self) -> &StreamApi {
-        &self.stream_api
+        &self.inner.stream_api
     }
 }
 
 impl Debug for PipewireClient {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "PipewireClient: {}", self.socket_path.to_str().unwrap())
+        writeln!(f, "PipewireClient: {}", self.inner.socket_path.to_str().unwrap())
     }
 }
 
-impl Drop for PipewireClient {
-    fn drop(&mut self) {
-        if self.internal_api.send_request_without_response(&MessageRequest::Quit).is_ok() {
-            if let Some(thread_handle) = self.thread_handle.take() {
-                thread_handle.join().unwrap();
+impl PipewireClientInner {
+    fn stop(&self) -> Result<(), Error> {
+        let thread_handle = match self.backend.lock().unwrap_or_else(|error| error.into_inner()).take() {
+            Some(PipewireClientBackend::Thread(value)) => value,
+            // Handlers are detached under loop lock, then loop is stopped
+            Some(PipewireClientBackend::ThreadLoop(value)) => {
+                drop(value);
+                return Ok(());
             }
-        } else {
-            panic!("Failed to send Quit message to PipeWire thread.");
+            None => return Ok(()),
+        };
+        self.internal_api.send_request_without_response(&MessageRequest::Quit)?;
+        // Last handle dropped from a callback run by PipeWire thread (e.g. a stream callback
+        // owning a clone), thread can't join itself. It exits once quit request is served.
+        if thread_handle.thread().id() == thread::current().id() {
+            return Ok(());
         }
+        thread_handle.join().map_err(|_| Error {
+            description: "PipeWire thread panicked".to_string(),
+        })
     }
-}
\ No newline at end of file
+}
+
+impl Drop for PipewireClientInner {
+    fn drop(&mut self) {
+        // Nothing left to report to, shutdown has to be used to get teardown errors
+        let _ = self.stop();
+    }
+}
@@ -1,15 +1,17 @@
 use crate::client::implementation::{CLIENT_INDEX, CLIENT_NAME_PREFIX};
 use crate::states::{MetadataState, NodeState};
-use crate::test_utils::fixtures::{client2, shared_client, PipewireTestClient};
-use crate::PipewireClient;
+use crate::test_utils::fixtures::{client2, isolated_client, shared_client, PipewireTestClient};
+use crate::{Direction, NodeCreateOptions, NodeInfo, PipewireClient, RemoteOptions, ShutdownOptions};
+use pipewire_spa_utils::audio::{AudioChannel, AudioChannelPosition};
 use rstest::rstest;
 use serial_test::serial;
 use std::any::TypeId;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
-use pipewire_test_utils::environment::TEST_ENVIRONMENT;
+use pipewire_test_utils::environment::{SHARED_SERVER, TEST_ENVIRONMENT};
 use pipewire_test_utils::server::{server_with_default_configuration, server_without_node, server_without_session_manager, Server};
 use crate::listeners::PipewireCoreSync;
 
@@ -18,11 +20,11 @@ use crate::listeners::PipewireCoreSync;
 pub fn names(
     #[from(client2)] (client_1, client_2): (PipewireTestClient, PipewireTestClient)
 ) {
-    let client_1_index = client_1.name.replace(format!("{}-", CLIENT_NAME_PREFIX).as_str(), "")
+    let client_1_index = client_1.name().replace(format!("{}-", CLIENT_NAME_PREFIX).as_str(), "")
         .parse::<u32>()
         .unwrap();
-    assert_eq!(format!("{}-{}", CLIENT_NAME_PREFIX, client_1_index), client_1.name);
-    assert_eq!(format!("{}-{}", CLIENT_NAME_PREFIX, client_1_index + 1), client_2.name);
+    assert_eq!(format!("{}-{}", CLIENT_NAME_PREFIX, client_1_index), client_1.name());
+    assert_eq!(format!("{}-{}", CLIENT_NAME_PREFIX, client_1_index + 1), client_2.name());
 }
 
 #[rstest]
@@ -40,6 +42,294 @@ fn init100(#[from(server_with_default_configuration)] _server: Arc<Server>) {
     }
 }
 
+fn assert_shareable<T: Send + Sync + Clone>() {}
+
+#[rstest]
+#[serial]
+pub fn concurrent_requests(#[from(shared_client)] client: PipewireTestClient) {
+    assert_shareable::<PipewireClient>();
+    let client = (**client).clone();
+    let handles = (0..4)
+        .map(|_| {
+            let client = client.clone();
+            thread::spawn(move || {
+                for _ in 0..10 {
+                    client.core().get_settings().unwrap();
+                    client.core().get_default_audio_nodes().unwrap();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[rstest]
+#[serial]
+pub fn shutdown(#[from(isolated_client)] client: PipewireTestClient) {
+    let node = client.node()
+        .create(NodeCreateOptions::new(
+            "shutdown".to_string(),
+            "shutdown".to_string(),
+            "shutdown".to_string(),
+            Direction::Output,
+            AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FR])
+        ))
+        .unwrap();
+    let report = (**client).clone().shutdown(ShutdownOptions::default()).unwrap();
+    assert_eq!(vec![node.id], report.destroyed_nodes);
+    assert_eq!(true, report.lingering_nodes.is_empty());
+    assert_eq!(true, report.errors.is_empty());
+    // Remaining handles are disconnected
+    client.core().get_settings().unwrap_err();
+}
+
+fn create_lingering_node(client: &PipewireTestClient) -> u32 {
+    client.node()
+        .create(
+            NodeCreateOptions::new(
+                "lingering".to_string(),
+                "lingering".to_string(),
+                "lingering".to_string(),
+                Direction::Output,
+                AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FR])
+            )
+            .with_linger(true)
+        )
+        .unwrap()
+        .id
+}
+
+fn is_node_visible(client: &PipewireTestClient, id: u32, expected: bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let visible = client.node().get_by_id(id).is_ok();
+        if visible == expected || Instant::now() > deadline {
+            return visible;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[rstest]
+#[serial]
+pub fn shutdown_keeps_lingering_nodes(
+    #[from(client2)] (client_1, client_2): (PipewireTestClient, PipewireTestClient)
+) {
+    let id = create_lingering_node(&client_1);
+    let report = (**client_1).clone().shutdown(ShutdownOptions::default()).unwrap();
+    assert_eq!(vec![id], report.lingering_nodes);
+    assert_eq!(true, report.destroyed_nodes.is_empty());
+    assert_eq!(true, report.errors.is_empty());
+    // Server keeps node once its creator is disconnected
+    thread::sleep(Duration::from_millis(500));
+    assert_eq!(true, is_node_visible(&client_2, id, true));
+}
+
+#[rstest]
+#[serial]
+pub fn shutdown_destroys_lingering_nodes(
+    #[from(client2)] (client_1, client_2): (PipewireTestClient, PipewireTestClient)
+) {
+    let id = create_lingering_node(&client_1);
+    assert_eq!(true, is_node_visible(&client_2, id, true));
+    let report = (**client_1).clone()
+        .shutdown(ShutdownOptions::new().with_keep_lingering_nodes(false))
+        .unwrap();
+    assert_eq!(vec![id], report.destroyed_nodes);
+    assert_eq!(true, report.lingering_nodes.is_empty());
+    assert_eq!(true, report.errors.is_empty());
+    assert_eq!(false, is_node_visible(&client_2, id, false));
+}
+
+#[rstest]
+#[serial]
+pub fn with_thread_loop(#[from(server_with_default_configuration)] _server: Arc<Server>) {
+    let client = PipewireClient::with_thread_loop(
+        Arc::new(Runtime::new().unwrap()),
+        TEST_ENVIRONMENT.lock().unwrap().client_timeout.clone(),
+        RemoteOptions::default(),
+    ).unwrap();
+    client.core().get_settings().unwrap();
+    let node = client.node()
+        .create(NodeCreateOptions::new(
+            "thread-loop".to_string(),
+            "thread-loop".to_string(),
+            "thread-loop".to_string(),
+            Direction::Output,
+            AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FR])
+        ))
+        .unwrap();
+    let handle = client.clone();
+    let report = client.shutdown(ShutdownOptions::default()).unwrap();
+    assert_eq!(vec![node.id], report.destroyed_nodes);
+    // Loop is stopped, remaining handles are disconnected
+    handle.core().get_settings().unwrap_err();
+}
+
+#[rstest]
+#[serial]
+pub fn attach_thread_loop(#[from(server_with_default_configuration)] _server: Arc<Server>) {
+    let thread_loop = unsafe { pipewire::thread_loop::ThreadLoop::new(None, None) }.unwrap();
+    let lock = thread_loop.lock();
+    let (client, attachment) = PipewireClient::attach(
+        &thread_loop,
+        Arc::new(Runtime::new().unwrap()),
+        TEST_ENVIRONMENT.lock().unwrap().client_timeout.clone(),
+        RemoteOptions::default(),
+    ).unwrap();
+    drop(lock);
+    thread_loop.start();
+    client.wait_ready().unwrap();
+    client.core().get_settings().unwrap();
+    let lock = thread_loop.lock();
+    drop(attachment);
+    drop(lock);
+    thread_loop.stop();
+    // Handlers are detached, requests are no longer served
+    client.core().get_settings().unwrap_err();
+}
+
+fn find_node_by_name(client: &PipewireClient, name: &str) -> Vec<NodeInfo> {
+    let name = name.to_string();
+    client.node()
+        .find(move |properties| {
+            properties.get(*pipewire::keys::NODE_NAME) == Some(&name)
+        })
+        .unwrap()
+}
+
+#[rstest]
+#[serial]
+pub fn remotes(#[from(server_with_default_configuration)] server: Arc<Server>) {
+    let shared_server = SHARED_SERVER.clone();
+    // Local servers are resolved from environment, both clients would share the same one
+    let (Some(socket_path_1), Some(socket_path_2)) = (shared_server.socket_path(), server.socket_path()) else {
+        return;
+    };
+    let remotes = [
+        ("remote-1", socket_path_1),
+        ("remote-2", socket_path_2),
+    ];
+    let handles = remotes
+        .into_iter()
+        .map(|(name, socket_path)| thread::spawn(move || {
+            let client = PipewireClient::connect(
+                Arc::new(Runtime::new().unwrap()),
+                TEST_ENVIRONMENT.lock().unwrap().client_timeout.clone(),
+                RemoteOptions::new()
+                    .with_name(name.to_string())
+                    .with_socket_path(socket_path),
+            ).unwrap();
+            assert_eq!(name, client.name());
+            client
+        }))
+        .collect::<Vec<_>>();
+    let clients = handles.into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect::<Vec<_>>();
+    let node_name = "remote-1-node";
+    clients[0].node()
+        .create(NodeCreateOptions::new(
+            node_name.to_string(),
+            node_name.to_string(),
+            node_name.to_string(),
+            Direction::Output,
+            AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FR])
+        ))
+        .unwrap();
+    assert_eq!(1, find_node_by_name(&clients[0], node_name).len());
+    // Node is only registered by the server it was created on
+    thread::sleep(Duration::from_millis(500));
+    assert_eq!(true, find_node_by_name(&clients[1], node_name).is_empty());
+}
+
+#[rstest]
+#[serial]
+pub fn relative_socket_path(#[from(server_with_default_configuration)] _server: Arc<Server>) {
+    let error = PipewireClient::connect(
+        Arc::new(Runtime::new().unwrap()),
+        TEST_ENVIRONMENT.lock().unwrap().client_timeout.clone(),
+        RemoteOptions::new().with_socket_path(PathBuf::from("pipewire-0")),
+    ).unwrap_err();
+    assert_eq!("Socket path(pipewire-0) is not absolute", error.description);
+}
+
+#[rstest]
+#[serial]
+pub fn errors(#[from(shared_client)] client: PipewireTestClient) {
+    // Reading errors drains storage
+    let _ = client.errors();
+    assert_eq!(0, client.errors().len());
+    client.core().provoke_core_error().unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let errors = loop {
+        let errors = client.errors();
+        if errors.is_empty() == false || Instant::now() > deadline {
+            break errors;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    assert_eq!(true, errors.iter().any(|error| error.description.starts_with("Server error:")));
+    // Client is still usable after an error outside of a request
+    client.core().get_settings().unwrap();
+}
+
+#[rstest]
+#[serial]
+pub fn ready_with_failed_node_format(#[from(isolated_client)] client: PipewireTestClient) {
+    let node = client.node()
+        .find(|_| true)
+        .unwrap()
+        .into_iter()
+        .find(|node| node.direction.is_some())
+        .unwrap();
+    let _ = client.errors();
+    client.core().provoke_node_format_error(node.id).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let errors = loop {
+        let errors = client.errors();
+        if errors.is_empty() == false || Instant::now() > deadline {
+            break errors;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    assert_eq!(true, errors.iter().any(|error| error.description == "Provoked node format error"));
+    // Failed node is left out instead of keeping client waiting for its format
+    client.core().wait_ready().unwrap();
+    let ids: Vec<u32> = client.node()
+        .find(|_| true)
+        .unwrap()
+        .into_iter()
+        .map(|node| node.id)
+        .collect();
+    assert_eq!(false, ids.contains(&node.id));
+}
+
+#[rstest]
+#[serial]
+pub fn errors_before_ready(#[from(server_with_default_configuration)] _server: Arc<Server>) {
+    let thread_loop = unsafe { pipewire::thread_loop::ThreadLoop::new(None, None) }.unwrap();
+    let lock = thread_loop.lock();
+    let (client, attachment) = PipewireClient::attach(
+        &thread_loop,
+        Arc::new(Runtime::new().unwrap()),
+        TEST_ENVIRONMENT.lock().unwrap().client_timeout.clone(),
+        RemoteOptions::default(),
+    ).unwrap();
+    drop(lock);
+    thread_loop.start();
+    // Draining errors doesn't consume initialization message
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(0, client.errors().len());
+    client.wait_ready().unwrap();
+    let lock = thread_loop.lock();
+    drop(attachment);
+    drop(lock);
+    thread_loop.stop();
+}
+
 #[rstest]
 #[serial]
 pub fn with_default_configuration(#[from(shared_client)] client: PipewireTestClient) {
@@ -54,8 +344,9 @@ pub fn with_default_configuration(#[from(shared_client)] client: PipewireTestCli
     for (_, listeners) in metadata_listeners {
         assert_eq!(0, listeners.len());
     }
+    // Info listener is kept during node lifetime
     for (_, listeners) in nodes_listeners {
-        assert_eq!(0, listeners.len());
+        assert_eq!(vec!["info".to_string()], *listeners);
     }
 }
 
@@ -1,5 +1,6 @@
 mod implementation;
 pub use implementation::PipewireClient;
+pub use handlers::PipewireLoopAttachment;
 mod connection_string;
 mod handlers;
 mod api;
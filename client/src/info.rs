@@ -1,8 +1,44 @@
+use crate::error::Error;
 use crate::utils::Direction;
+use pipewire_spa_utils::audio::compressed::{AudioInfoMp3, AudioInfoRa, AudioInfoVorbis};
+#[cfg(feature = "v0_3_65")]
+use pipewire_spa_utils::audio::compressed::{AudioInfoAac, AudioInfoAlac, AudioInfoApe, AudioInfoFlac};
+#[cfg(feature = "v0_3_75")]
+use pipewire_spa_utils::audio::compressed::AudioInfoOpus;
+use pipewire_spa_utils::audio::dsd::AudioInfoDsd;
+use pipewire_spa_utils::audio::iec958::AudioInfoIec958;
 use pipewire_spa_utils::audio::raw::AudioInfoRaw;
+use pipewire_spa_utils::control::ControlInfo;
 use pipewire_spa_utils::audio::AudioChannelPosition;
 use pipewire_spa_utils::audio::AudioSampleFormat;
 use pipewire_spa_utils::format::{MediaSubtype, MediaType};
+use pipewire_spa_utils::utils::choice::SpaChoice;
+use pipewire_spa_utils::video::dsp::VideoInfoDsp;
+use pipewire_spa_utils::video::raw::VideoInfoRaw;
+use pipewire::spa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use std::collections::HashMap;
+use std::io::{Seek, Write};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeStatus {
+    Creating,
+    Suspended,
+    Idle,
+    Running,
+    Error(String),
+}
+
+impl From<pipewire::node::NodeState<'_>> for NodeStatus {
+    fn from(value: pipewire::node::NodeState<'_>) -> Self {
+        match value {
+            pipewire::node::NodeState::Creating => NodeStatus::Creating,
+            pipewire::node::NodeState::Suspended => NodeStatus::Suspended,
+            pipewire::node::NodeState::Idle => NodeStatus::Idle,
+            pipewire::node::NodeState::Running => NodeStatus::Running,
+            pipewire::node::NodeState::Error(value) => NodeStatus::Error(value.to_string()),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct NodeInfo {
@@ -10,13 +46,54 @@ pub struct NodeInfo {
     pub name: String,
     pub description: String,
     pub nickname: String,
-    pub direction: Direction,
+    /// None when node is not an audio sink/source
+    pub direction: Option<Direction>,
     pub is_default: bool,
-    pub format: AudioInfoRaw
+    /// Only retrieved for audio sink/source nodes
+    pub format: Option<AudioInfoRaw>,
+    pub state: NodeStatus,
+    pub max_input_ports: u32,
+    pub max_output_ports: u32,
+    pub device_id: Option<u32>,
+    pub serial: Option<u64>,
+    pub client_id: Option<u32>,
+    pub properties: HashMap<String, String>,
+}
+
+/// Resources released by PipewireClient::shutdown
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownReport {
+    /// Streams disconnected (if needed) then destroyed
+    pub streams: Vec<String>,
+    pub destroyed_nodes: Vec<u32>,
+    /// Created nodes left to server since they were created with object.linger
+    pub lingering_nodes: Vec<u32>,
+    /// Proxies of created objects that server had not registered yet
+    pub orphans: usize,
+    /// Teardown carries on after a failure, remaining resources are still released
+    pub errors: Vec<Error>,
+}
+
+/// Resources held by a client at a given time, counts growing steadily point to a leak
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diagnostics {
+    /// Active listeners, released ones are pruned beforehand
+    pub listeners: usize,
+    /// Proxies bound to server objects: metadata, nodes, streams and orphans. Clients are
+    /// tracked from registry without binding a proxy.
+    pub proxies: usize,
+    /// Proxies of created objects waiting to be registered or removed by server
+    pub orphans: usize,
+    pub nodes: usize,
+    pub streams: usize,
+    /// State conditions pending requests are waiting for
+    pub waiters: usize,
+    /// Requests sent by other handles that are still waiting for a response
+    pub pending_requests: usize,
 }
 
 #[derive(Debug, Clone)]
-pub struct AudioStreamInfo {
+pub struct AudioStreamRawInfo {
     pub media_type: MediaType,
     pub media_subtype: MediaSubtype,
     pub sample_format: AudioSampleFormat,
@@ -25,29 +102,183 @@ pub struct AudioStreamInfo {
     pub position: AudioChannelPosition
 }
 
-impl From<AudioInfoRaw> for AudioStreamInfo {
+impl From<AudioInfoRaw> for AudioStreamRawInfo {
     fn from(value: AudioInfoRaw) -> Self {
         Self {
             media_type: MediaType::Audio,
             media_subtype: MediaSubtype::Raw,
-            sample_format: value.sample_format.default,
-            sample_rate: value.sample_rate.value,
-            channels: *value.channels,
-            position: AudioChannelPosition::default(),
+            sample_format: value.sample_format.fixate(),
+            sample_rate: value.sample_rate.fixate(),
+            channels: value.channels.fixate(),
+            position: value.position,
+        }
+    }
+}
+
+impl From<AudioStreamRawInfo> for AudioInfoRaw {
+    fn from(value: AudioStreamRawInfo) -> Self {
+        Self {
+            media_type: value.media_type,
+            media_subtype: value.media_subtype,
+            sample_format: SpaChoice::None(value.sample_format),
+            sample_rate: SpaChoice::None(value.sample_rate),
+            channels: SpaChoice::None(value.channels),
+            position: value.position,
         }
     }
 }
 
-impl From<AudioStreamInfo> for pipewire::spa::param::audio::AudioInfoRaw {
-    fn from(value: AudioStreamInfo) -> Self {
+impl TryFrom<AudioStreamRawInfo> for pipewire::spa::param::audio::AudioInfoRaw {
+    type Error = Error;
+
+    fn try_from(value: AudioStreamRawInfo) -> Result<Self, Self::Error> {
         let format: pipewire::spa::sys::spa_audio_format = value.sample_format as u32;
         let format = pipewire::spa::param::audio::AudioFormat::from_raw(format);
-        let position: [u32; 64] = value.position.to_array();
+        let position: [u32; 64] = match value.position.to_array() {
+            Some(value) => value,
+            None => return Err(Error {
+                description: format!("Too many channels in position: {}", value.position.len()),
+            }),
+        };
         let mut info = pipewire::spa::param::audio::AudioInfoRaw::default();
         info.set_format(format);
         info.set_rate(value.sample_rate);
         info.set_channels(value.channels);
         info.set_position(position);
-        info
+        Ok(info)
+    }
+}
+
+/// Stream format, encoded variants are passed through as is (e.g. IEC958 to an HDMI sink)
+#[derive(Debug, Clone)]
+pub enum AudioStreamInfo {
+    Raw(AudioStreamRawInfo),
+    Iec958(AudioInfoIec958),
+    Dsd(AudioInfoDsd),
+    Mp3(AudioInfoMp3),
+    Vorbis(AudioInfoVorbis),
+    Ra(AudioInfoRa),
+    #[cfg(feature = "v0_3_65")]
+    Aac(AudioInfoAac),
+    #[cfg(feature = "v0_3_65")]
+    Alac(AudioInfoAlac),
+    #[cfg(feature = "v0_3_65")]
+    Flac(AudioInfoFlac),
+    #[cfg(feature = "v0_3_65")]
+    Ape(AudioInfoApe),
+    #[cfg(feature = "v0_3_75")]
+    Opus(AudioInfoOpus),
+}
+
+impl From<AudioInfoRaw> for AudioStreamInfo {
+    fn from(value: AudioInfoRaw) -> Self {
+        AudioStreamInfo::Raw(value.into())
     }
-}
\ No newline at end of file
+}
+
+macro_rules! impl_audio_stream_info_from {
+    (
+        $variant:ident,
+        $info:ident
+    ) => {
+        impl From<$info> for AudioStreamInfo {
+            fn from(value: $info) -> Self {
+                AudioStreamInfo::$variant(value)
+            }
+        }
+    }
+}
+
+impl_audio_stream_info_from!(Raw, AudioStreamRawInfo);
+impl_audio_stream_info_from!(Iec958, AudioInfoIec958);
+impl_audio_stream_info_from!(Dsd, AudioInfoDsd);
+impl_audio_stream_info_from!(Mp3, AudioInfoMp3);
+impl_audio_stream_info_from!(Vorbis, AudioInfoVorbis);
+impl_audio_stream_info_from!(Ra, AudioInfoRa);
+#[cfg(feature = "v0_3_65")]
+impl_audio_stream_info_from!(Aac, AudioInfoAac);
+#[cfg(feature = "v0_3_65")]
+impl_audio_stream_info_from!(Alac, AudioInfoAlac);
+#[cfg(feature = "v0_3_65")]
+impl_audio_stream_info_from!(Flac, AudioInfoFlac);
+#[cfg(feature = "v0_3_65")]
+impl_audio_stream_info_from!(Ape, AudioInfoApe);
+#[cfg(feature = "v0_3_75")]
+impl_audio_stream_info_from!(Opus, AudioInfoOpus);
+
+impl PodSerialize for AudioStreamInfo {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        match self {
+            AudioStreamInfo::Raw(value) => AudioInfoRaw::from(value.clone()).serialize(serializer),
+            AudioStreamInfo::Iec958(value) => value.serialize(serializer),
+            AudioStreamInfo::Dsd(value) => value.serialize(serializer),
+            AudioStreamInfo::Mp3(value) => value.serialize(serializer),
+            AudioStreamInfo::Vorbis(value) => value.serialize(serializer),
+            AudioStreamInfo::Ra(value) => value.serialize(serializer),
+            #[cfg(feature = "v0_3_65")]
+            AudioStreamInfo::Aac(value) => value.serialize(serializer),
+            #[cfg(feature = "v0_3_65")]
+            AudioStreamInfo::Alac(value) => value.serialize(serializer),
+            #[cfg(feature = "v0_3_65")]
+            AudioStreamInfo::Flac(value) => value.serialize(serializer),
+            #[cfg(feature = "v0_3_65")]
+            AudioStreamInfo::Ape(value) => value.serialize(serializer),
+            #[cfg(feature = "v0_3_75")]
+            AudioStreamInfo::Opus(value) => value.serialize(serializer),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum VideoStreamInfo {
+    Raw(VideoInfoRaw),
+    Dsp(VideoInfoDsp),
+}
+
+impl From<VideoInfoRaw> for VideoStreamInfo {
+    fn from(value: VideoInfoRaw) -> Self {
+        VideoStreamInfo::Raw(value)
+    }
+}
+
+impl From<VideoInfoDsp> for VideoStreamInfo {
+    fn from(value: VideoInfoDsp) -> Self {
+        VideoStreamInfo::Dsp(value)
+    }
+}
+
+impl PodSerialize for VideoStreamInfo {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        match self {
+            VideoStreamInfo::Raw(value) => value.serialize(serializer),
+            VideoStreamInfo::Dsp(value) => value.serialize(serializer),
+        }
+    }
+}
+
+/// Format a stream is created with, it also defines stream media type
+#[derive(Debug, Clone)]
+pub enum StreamInfo {
+    Audio(AudioStreamInfo),
+    Video(VideoStreamInfo),
+    Midi(ControlInfo),
+}
+
+impl PodSerialize for StreamInfo {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        match self {
+            StreamInfo::Audio(value) => value.serialize(serializer),
+            StreamInfo::Video(value) => value.serialize(serializer),
+            StreamInfo::Midi(value) => value.serialize(serializer),
+        }
+    }
+}
@@ -5,18 +5,31 @@ pub use pipewire_common::constants as constants;
 
 mod client;
 pub use client::PipewireClient;
+pub use client::PipewireLoopAttachment;
 
 mod listeners;
 mod messages;
 mod states;
 
 mod info;
+mod options;
 
 #[cfg(test)]
 pub mod test_utils;
 
 pub use info::AudioStreamInfo;
+pub use info::AudioStreamRawInfo;
+pub use info::VideoStreamInfo;
+pub use info::StreamInfo;
 pub use info::NodeInfo;
+pub use info::NodeStatus;
+pub use info::ShutdownReport;
+pub use info::Diagnostics;
+pub use options::NodeCreateOptions;
+pub use options::CallOptions;
+pub use options::RemoteOptions;
+pub use options::ShutdownOptions;
+pub use tokio_util::sync::CancellationToken;
 
 pub use pipewire as pipewire;
 pub use pipewire_spa_utils as spa_utils;
@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::rc::Rc;
 
@@ -60,19 +60,19 @@ impl<L> Listeners<L> {
         listeners.insert(name, listener);
     }
 
-    pub fn triggered(&mut self, name: &String) {
-        let mut listeners = self.listeners.borrow_mut();
-        let listener = listeners.get_mut(name).unwrap();
-        if listener.control_flow.borrow().is_released == false {
-            return;
-        }
-        listeners.remove(name);
+    /// Released listeners cannot be dropped from their own callback, they are pruned
+    /// afterward from the loop, outside any PipeWire event.
+    pub fn prune(&mut self) {
+        self.listeners
+            .borrow_mut()
+            .retain(|_, listener| listener.control_flow.borrow().is_released() == false);
     }
 }
 
 pub(super) struct PipewireCoreSync {
     core: Rc<RefCell<pipewire::core::Core>>,
     listeners: Rc<RefCell<Listeners<pipewire::core::Listener>>>,
+    next_seq: Rc<Cell<u32>>,
 }
 
 impl PipewireCoreSync {
@@ -80,6 +80,7 @@ impl PipewireCoreSync {
         Self {
             core,
             listeners: Rc::new(RefCell::new(Listeners::new())),
+            next_seq: Rc::new(Cell::new(0)),
         }
     }
 
@@ -87,14 +88,19 @@ impl PipewireCoreSync {
         self.listeners.borrow().get_names()
     }
 
-    pub fn register<F>(&self, seq: u32, callback: F)
+    pub(super) fn prune_listeners(&self) {
+        self.listeners.borrow_mut().prune();
+    }
+
+    /// Each registration has its own sequence number, concurrent syncs cannot be mixed up
+    pub fn register<F>(&self, callback: F)
     where
         F: Fn(&mut ListenerControlFlow) + 'static,
     {
+        let seq = self.next_seq.get();
+        self.next_seq.set(seq.wrapping_add(1));
         let sync_id = self.core.borrow_mut().sync(seq as i32).unwrap();
         let name = format!("sync-{}", sync_id.raw());
-        let listeners = self.listeners.clone();
-        let listener_name = name.clone();
         let control_flow = Rc::new(RefCell::new(ListenerControlFlow::new()));
         let listener_control_flow = control_flow.clone();
         let listener = self
@@ -109,7 +115,6 @@ impl PipewireCoreSync {
                     return;
                 }
                 callback(&mut listener_control_flow.borrow_mut());
-                listeners.borrow_mut().triggered(&listener_name);
             })
             .register();
         self.listeners
@@ -123,6 +128,7 @@ impl Clone for PipewireCoreSync {
         Self {
             core: self.core.clone(),
             listeners: self.listeners.clone(),
+            next_seq: self.next_seq.clone(),
         }
     }
 }
\ No newline at end of file
@@ -1,7 +1,8 @@
 use crate::error::Error;
-use crate::info::{AudioStreamInfo, NodeInfo};
+use crate::info::{Diagnostics, NodeInfo, NodeStatus, ShutdownReport, StreamInfo};
 use crate::listeners::ListenerControlFlow;
-use crate::states::{DefaultAudioNodesState, GlobalId, GlobalObjectState, SettingsState};
+use crate::options::{NodeCreateOptions, ShutdownOptions};
+use crate::states::{DefaultAudioNodesState, GlobalId, SettingsState};
 use crate::utils::Direction;
 use pipewire_spa_utils::audio::raw::AudioInfoRaw;
 use std::collections::HashMap;
@@ -37,9 +38,39 @@ impl Clone for StreamCallback {
     }
 }
 
+pub(super) struct NodePredicate {
+    predicate: Arc<Box<dyn Fn(&HashMap<String, String>) -> bool + Send + Sync + 'static>>
+}
+
+impl <F: Fn(&HashMap<String, String>) -> bool + Send + Sync + 'static> From<F> for NodePredicate {
+    fn from(value: F) -> Self {
+        Self { predicate: Arc::new(Box::new(value)) }
+    }
+}
+
+impl NodePredicate {
+    pub fn call(&self, properties: &HashMap<String, String>) -> bool {
+        (self.predicate)(properties)
+    }
+}
+
+impl Debug for NodePredicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodePredicate").finish()
+    }
+}
+
+impl Clone for NodePredicate {
+    fn clone(&self) -> Self {
+        Self { predicate: self.predicate.clone() }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(super) enum MessageRequest {
     Quit,
+    /// Release streams, created nodes and orphans before quitting
+    Shutdown(ShutdownOptions),
     Settings,
     DefaultAudioNodes,
     // Node
@@ -47,62 +78,82 @@ pub(super) enum MessageRequest {
         name: String,
         direction: Direction,
     },
-    CreateNode {
-        name: String,
-        description: String,
-        nickname: String,
-        direction: Direction,
-        channels: u16,
-    },
+    GetNodeById(GlobalId),
+    GetNodeBySerial(u64),
+    FindNodes(NodePredicate),
+    CreateNode(NodeCreateOptions),
     DeleteNode(GlobalId),
     EnumerateNodes(Direction),
+    UpdateNodeProperties {
+        id: GlobalId,
+        properties: HashMap<String, String>,
+    },
+    SuspendNode(GlobalId),
+    ResumeNode(GlobalId),
     // Stream
     CreateStream {
         node_id: GlobalId,
         direction: Direction,
-        format: AudioStreamInfo,
+        format: StreamInfo,
         callback: StreamCallback,
     },
     DeleteStream(String),
     ConnectStream(String),
     DisconnectStream(String),
+    /// Answered once stream format is negotiated with its peer
+    GetStreamFormat(String),
     // Internal requests
     CheckSessionManagerRegistered,
-    SettingsState,
-    DefaultAudioNodesState,
-    NodeState(GlobalId),
-    NodeStates,
     NodeCount,
+    /// Answered once node is initialized, i.e. its properties and format are known
+    WaitNodeInitialized(GlobalId),
+    /// Answered once settings, default audio nodes and all nodes are initialized
+    WaitReady,
+    /// Resources held by PipeWire thread
+    Diagnostics,
+    /// Names of listeners still active, grouped by object
     #[cfg(test)]
-    Listeners
+    Listeners,
+    /// Create an object from an unknown factory, server rejects it with an error
+    /// reported outside of any request
+    #[cfg(test)]
+    ProvokeCoreError,
+    /// Handle node as if its EnumFormat could not be deserialized
+    #[cfg(test)]
+    ProvokeNodeFormatError(GlobalId),
 }
 
 #[derive(Debug, Clone)]
 pub(super) enum MessageResponse {
     Error(Error),
     Initialized,
+    Shutdown(ShutdownReport),
     Settings(SettingsState),
     DefaultAudioNodes(DefaultAudioNodesState),
     // Nodes
     GetNode(NodeInfo),
+    FindNodes(Vec<NodeInfo>),
     CreateNode(GlobalId),
     DeleteNode,
     EnumerateNodes(Vec<NodeInfo>),
+    UpdateNodeProperties,
+    SuspendNode,
+    ResumeNode,
     // Streams
     CreateStream(String),
     DeleteStream,
     ConnectStream,
     DisconnectStream,
+    GetStreamFormat(StreamInfo),
     // Internals responses
     CheckSessionManagerRegistered {
         session_manager_registered: bool,
         error: Option<Error>,
     },
-    SettingsState(GlobalObjectState),
-    DefaultAudioNodesState(GlobalObjectState),
-    NodeState(GlobalObjectState),
-    NodeStates(Vec<GlobalObjectState>),
     NodeCount(u32),
+    NodeInitialized(GlobalId),
+    Ready,
+    Diagnostics(Diagnostics),
     // For testing purpose only
     #[cfg(test)]
     Listeners {
@@ -110,7 +161,12 @@ pub(super) enum MessageResponse {
         metadata: HashMap<String, Vec<String>>,
         nodes: HashMap<String, Vec<String>>,
         streams: HashMap<String, Vec<String>>,
-    }
+        orphans: HashMap<String, Vec<String>>,
+    },
+    #[cfg(test)]
+    ProvokeCoreError,
+    #[cfg(test)]
+    ProvokeNodeFormatError,
 }
 
 #[derive(Debug, Clone)]
@@ -135,4 +191,19 @@ pub(super) enum EventMessage {
         id: GlobalId,
         format: AudioInfoRaw,
     },
+    SetNodeFormatError {
+        id: GlobalId,
+        error: Error,
+    },
+    SetNodeInfo {
+        id: GlobalId,
+        status: NodeStatus,
+        max_input_ports: u32,
+        max_output_ports: u32,
+        properties: Option<HashMap<String, String>>,
+    },
+    SetStreamFormat {
+        name: String,
+        format: StreamInfo,
+    },
 }
\ No newline at end of file
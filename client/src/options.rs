@@ -0,0 +1,196 @@
+use crate::constants::{FACTORY_NAME_PROPERTY_VALUE_NULL_AUDIO_SINK, MONITOR_CHANNEL_VOLUMES_PROPERTY_KEY, MONITOR_PASSTHROUGH_PROPERTY_KEY};
+use crate::utils::Direction;
+use crate::error::Error;
+use pipewire_spa_utils::audio::{AudioChannelLayout, AudioChannelPosition, AudioSampleFormat};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, Clone)]
+pub struct NodeCreateOptions {
+    pub name: String,
+    pub description: String,
+    pub nickname: String,
+    pub direction: Direction,
+    pub position: AudioChannelPosition,
+    pub sample_rate: Option<u32>,
+    pub sample_format: Option<AudioSampleFormat>,
+    pub linger: bool,
+    pub factory_name: String,
+    pub properties: HashMap<String, String>,
+}
+
+impl NodeCreateOptions {
+    pub fn new(
+        name: String,
+        description: String,
+        nickname: String,
+        direction: Direction,
+        position: AudioChannelPosition,
+    ) -> Self {
+        let mut properties = HashMap::new();
+        properties.insert(MONITOR_CHANNEL_VOLUMES_PROPERTY_KEY.to_string(), "true".to_string());
+        properties.insert(MONITOR_PASSTHROUGH_PROPERTY_KEY.to_string(), "true".to_string());
+        Self {
+            name,
+            description,
+            nickname,
+            direction,
+            position,
+            sample_rate: None,
+            sample_format: None,
+            linger: false,
+            factory_name: FACTORY_NAME_PROPERTY_VALUE_NULL_AUDIO_SINK.to_string(),
+            properties,
+        }
+    }
+
+    /// Position is the usual layout for channels count, see [`AudioChannelLayout::from_channels_count`]
+    pub fn from_channels_count(
+        name: String,
+        description: String,
+        nickname: String,
+        direction: Direction,
+        channels: u32,
+    ) -> Result<Self, Error> {
+        let layout = AudioChannelLayout::from_channels_count(channels)
+            .ok_or(Error {
+                description: format!("No usual layout for {} channels", channels),
+            })?;
+        let position = AudioChannelPosition::try_from(layout)
+            .map_err(|error| Error {
+                description: error.to_string(),
+            })?;
+        Ok(Self::new(name, description, nickname, direction, position))
+    }
+
+    pub fn channels(&self) -> u32 {
+        self.position.len() as u32
+    }
+
+    pub fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+
+    pub fn with_sample_format(mut self, sample_format: AudioSampleFormat) -> Self {
+        self.sample_format = Some(sample_format);
+        self
+    }
+
+    /// When set, node is kept by server after client disconnection (i.e. object.linger)
+    pub fn with_linger(mut self, linger: bool) -> Self {
+        self.linger = linger;
+        self
+    }
+
+    pub fn with_factory_name(mut self, factory_name: String) -> Self {
+        self.factory_name = factory_name;
+        self
+    }
+
+    /// Extra properties override default ones (e.g. monitor.channel-volumes). Properties set
+    /// through other options (e.g. node.name or audio.position) are rejected on creation.
+    pub fn with_property(mut self, key: String, value: String) -> Self {
+        self.properties.insert(key, value);
+        self
+    }
+
+    pub fn with_properties(mut self, properties: HashMap<String, String>) -> Self {
+        self.properties.extend(properties);
+        self
+    }
+}
+
+/// Per-call options, client timeout is used when none is set
+#[derive(Debug, Clone, Default)]
+pub struct CallOptions {
+    pub timeout: Option<Duration>,
+    pub cancellation_token: Option<CancellationToken>,
+}
+
+impl CallOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Cancelling token makes pending call return immediately, its late response is discarded
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+
+    /// Options of a call sub-request, its timeout is the time left until call deadline
+    pub(crate) fn until(&self, deadline: Instant) -> Self {
+        Self {
+            timeout: Some(deadline.saturating_duration_since(Instant::now())),
+            cancellation_token: self.cancellation_token.clone(),
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        match &self.cancellation_token {
+            Some(value) => value.is_cancelled(),
+            None => false,
+        }
+    }
+}
+
+/// Remote a client connects to. Unset values fall back to generated name and
+/// process environment (i.e. $PIPEWIRE_RUNTIME_DIR or $XDG_RUNTIME_DIR, and $PIPEWIRE_REMOTE).
+#[derive(Debug, Clone, Default)]
+pub struct RemoteOptions {
+    pub name: Option<String>,
+    pub socket_path: Option<PathBuf>,
+}
+
+impl RemoteOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Application name the server knows the client by
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Absolute path of server socket, environment is not read when set. A relative path is
+    /// rejected on connection.
+    pub fn with_socket_path(mut self, socket_path: PathBuf) -> Self {
+        self.socket_path = Some(socket_path);
+        self
+    }
+}
+
+/// Options of [`crate::PipewireClient::shutdown`]
+#[derive(Debug, Clone)]
+pub struct ShutdownOptions {
+    /// Created nodes with object.linger are left to server, they outlive the client
+    pub keep_lingering_nodes: bool,
+}
+
+impl Default for ShutdownOptions {
+    fn default() -> Self {
+        Self {
+            keep_lingering_nodes: true,
+        }
+    }
+}
+
+impl ShutdownOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_keep_lingering_nodes(mut self, keep_lingering_nodes: bool) -> Self {
+        self.keep_lingering_nodes = keep_lingering_nodes;
+        self
+    }
+}
@@ -3,13 +3,20 @@ use crate::error::Error;
 use crate::listeners::{Listener, ListenerControlFlow, Listeners};
 use crate::messages::StreamCallback;
 use crate::utils::dict_ref_to_hashmap;
+use crate::info::{Diagnostics, NodeStatus, StreamInfo};
 use crate::Direction;
 use pipewire::spa::utils::dict::ParsableValue;
+use pipewire_spa_utils::audio::dsd::AudioInfoDsd;
+use pipewire_spa_utils::audio::iec958::AudioInfoIec958;
 use pipewire_spa_utils::audio::raw::AudioInfoRaw;
 use pipewire_spa_utils::audio::AudioChannel;
+use pipewire_spa_utils::control::ControlInfo;
 use pipewire_spa_utils::format::{MediaSubtype, MediaType};
+use pipewire_spa_utils::video::dsp::VideoInfoDsp;
+use pipewire_spa_utils::video::raw::VideoInfoRaw;
+use pipewire_spa_utils::param::props::Props;
 use std::cell::{Cell, RefCell};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::io::Cursor;
 use std::rc::Rc;
@@ -59,11 +66,38 @@ impl Display for GlobalId {
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub(super) enum GlobalObjectState {
     Pending,
-    Initialized
+    Initialized,
+    /// Server data could not be parsed, object would never be initialized
+    Failed,
+}
+
+/// State changes a request could wait for, instead of polling objects state
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(super) enum StateCondition {
+    NodeInitialized(GlobalId),
+    /// Server reported node properties holding every given value
+    NodeProperties(GlobalId, HashMap<String, String>),
+    /// Server reported node status matching suspended flag, node in error state fails waiter
+    NodeSuspended(GlobalId, bool),
+    /// Stream format negotiated with its peer
+    StreamFormat(String),
+    /// Settings, default audio nodes and all nodes are initialized
+    Ready,
+}
+
+pub(super) struct StateWaiter {
+    condition: StateCondition,
+    callback: Box<dyn FnOnce(Result<(), Error>)>,
 }
 
 pub(super) struct GlobalState {
     orphans: Rc<RefCell<HashMap<usize, OrphanState>>>,
+    next_orphan_index: usize,
+    waiters: Vec<StateWaiter>,
+    /// Properties requested for created nodes, kept until server registers them
+    created_nodes: HashMap<GlobalId, HashMap<String, String>>,
+    /// Nodes created through this client, released on shutdown
+    client_nodes: HashSet<GlobalId>,
     clients: HashMap<GlobalId, ClientState>,
     metadata: HashMap<GlobalId, MetadataState>,
     nodes: HashMap<GlobalId, NodeState>,
@@ -73,12 +107,12 @@ pub(super) struct GlobalState {
 }
 
 impl GlobalState {
+    /// Orphan is dropped once server removed its proxy, see [`GlobalState::prune_listeners`]
     pub fn insert_orphan(&mut self, mut state: OrphanState) {
-        let index = std::ptr::addr_of!(state) as usize;
-        let listener_orphans = self.orphans.clone();
+        let index = self.next_orphan_index;
+        self.next_orphan_index = self.next_orphan_index.wrapping_add(1);
         state.add_removed_listener(
             move |control_flow| {
-                listener_orphans.borrow_mut().remove(&index);
                 control_flow.release()
             }
         );
@@ -141,24 +175,43 @@ impl GlobalState {
         Ok(metadatas)
     }
 
-    pub fn insert_node(&mut self, id: GlobalId, state: NodeState) -> Result<(), Error> {
+    pub fn insert_node(&mut self, id: GlobalId, mut state: NodeState) -> Result<(), Error> {
         if self.nodes.contains_key(&id) {
             return Err(Error {
                 description: format!("Node with id({}) already exists", id),
             });
         }
+        if let Some(properties) = self.created_nodes.remove(&id) {
+            state.set_properties(properties);
+        }
         self.nodes.insert(id, state);
         Ok(())
     }
 
-    pub fn delete_node(&mut self, id: &GlobalId) -> Result<(), Error> {
-        if self.nodes.contains_key(id) == false {
-            return Err(Error {
-                description: format!("Node with id({}) not found", id),
-            });
+    /// Created node proxy is bound to its global id before or after registry announced it
+    pub fn set_created_node_properties(&mut self, id: GlobalId, properties: HashMap<String, String>) {
+        self.client_nodes.insert(id.clone());
+        match self.nodes.get_mut(&id) {
+            Some(node) => node.set_properties(properties),
+            None => {
+                self.created_nodes.insert(id, properties);
+            }
         }
-        self.nodes.remove(id);
-        Ok(())
+    }
+
+    pub fn delete_node(&mut self, id: &GlobalId) -> Result<(), Error> {
+        self.take_node(id).map(|_| ())
+    }
+
+    pub fn take_node(&mut self, id: &GlobalId) -> Result<NodeState, Error> {
+        self.client_nodes.remove(id);
+        self.nodes.remove(id).ok_or(Error {
+            description: format!("Node with id({}) not found", id),
+        })
+    }
+
+    pub fn get_client_node_ids(&self) -> Vec<GlobalId> {
+        self.client_nodes.iter().cloned().collect()
     }
 
     pub fn get_node(&self, id: &GlobalId) -> Result<&NodeState, Error> {
@@ -252,6 +305,144 @@ impl GlobalState {
     pub fn remove(&mut self, id: &GlobalId) {
         self.metadata.remove(id);
         self.nodes.remove(id);
+        self.created_nodes.remove(id);
+        self.client_nodes.remove(id);
+    }
+
+    /// Drop proxies of created objects not yet registered, return how many were removed
+    pub fn clear_orphans(&mut self) -> usize {
+        let orphans = std::mem::take(&mut *self.orphans.borrow_mut());
+        orphans.len()
+    }
+
+    #[cfg(test)]
+    pub fn get_orphans_listener_names(&self) -> HashMap<String, Vec<String>> {
+        self.orphans.borrow()
+            .iter()
+            .map(|(index, orphan)| (index.to_string(), orphan.get_listener_names()))
+            .collect()
+    }
+
+    /// Drop released listeners and orphans removed by server. Listeners are only released
+    /// from their own callback, hence it has to be called outside any PipeWire event.
+    pub fn prune_listeners(&mut self) {
+        self.orphans.borrow_mut().retain(|_, orphan| orphan.is_removed() == false);
+        for orphan in self.orphans.borrow().values() {
+            orphan.prune_listeners();
+        }
+        for metadata in self.metadata.values() {
+            metadata.prune_listeners();
+        }
+        for node in self.nodes.values() {
+            node.prune_listeners();
+        }
+        for stream in self.streams.values() {
+            stream.prune_listeners();
+        }
+    }
+
+    fn check_condition(&self, condition: &StateCondition) -> Option<Result<(), Error>> {
+        match condition {
+            StateCondition::NodeInitialized(id) => match self.nodes.get(id) {
+                Some(node) if node.state() == GlobalObjectState::Initialized => Some(Ok(())),
+                Some(node) if node.state() == GlobalObjectState::Failed => Some(Err(Error {
+                    description: format!(
+                        "Node with id({}) failed to initialize: {}",
+                        id,
+                        node.format_error().map(|error| error.description).unwrap_or_default()
+                    ),
+                })),
+                Some(_) => None,
+                // Created node is bound but not yet announced by registry
+                None if self.created_nodes.contains_key(id) => None,
+                // Node was removed before being initialized (e.g. "object.register" set to "false")
+                None => Some(Err(Error {
+                    description: format!("Node with id({}) not found", id),
+                })),
+            },
+            StateCondition::NodeProperties(id, properties) => match self.nodes.get(id) {
+                Some(node) if node.has_properties(properties) => Some(Ok(())),
+                Some(_) => None,
+                None => Some(Err(Error {
+                    description: format!("Node with id({}) not found", id),
+                })),
+            },
+            StateCondition::NodeSuspended(id, suspended) => match self.nodes.get(id) {
+                Some(node) => match node.status() {
+                    NodeStatus::Error(value) => Some(Err(Error {
+                        description: format!("Node with id({}) in error state: {}", id, value),
+                    })),
+                    status if (status == NodeStatus::Suspended) == *suspended => Some(Ok(())),
+                    _ => None,
+                },
+                None => Some(Err(Error {
+                    description: format!("Node with id({}) not found", id),
+                })),
+            },
+            StateCondition::StreamFormat(name) => match self.streams.get(name) {
+                Some(stream) if stream.negotiated_format().borrow().is_some() => Some(Ok(())),
+                Some(_) => None,
+                None => Some(Err(Error {
+                    description: format!("Stream with name({}) not found", name),
+                })),
+            },
+            StateCondition::Ready => {
+                let is_ready = self.settings.state == GlobalObjectState::Initialized
+                    && self.default_audio_nodes.state == GlobalObjectState::Initialized
+                    // Failed nodes are left out, waiting for them would never end
+                    && self.nodes.values().all(|node| node.state() != GlobalObjectState::Pending);
+                match is_ready {
+                    true => Some(Ok(())),
+                    false => None,
+                }
+            }
+        }
+    }
+
+    /// Callback is called right away if condition is already met, otherwise once a state change
+    /// notified with notify_waiters meets it
+    pub fn add_waiter<F>(&mut self, condition: StateCondition, callback: F)
+    where
+        F: FnOnce(Result<(), Error>) + 'static
+    {
+        match self.check_condition(&condition) {
+            Some(result) => callback(result),
+            None => self.waiters.push(StateWaiter {
+                condition,
+                callback: Box::new(callback),
+            }),
+        }
+    }
+
+    /// Resources held by this state, listeners outside of it (e.g. core sync ones) are not counted
+    pub fn diagnostics(&self) -> Diagnostics {
+        let orphans = self.orphans.borrow();
+        let listeners = orphans.values().map(|orphan| orphan.get_listener_names().len()).sum::<usize>()
+            + self.metadata.values().map(|metadata| metadata.get_listener_names().len()).sum::<usize>()
+            + self.nodes.values().map(|node| node.get_listener_names().len()).sum::<usize>()
+            + self.streams.values().map(|stream| stream.get_listener_names().len()).sum::<usize>();
+        Diagnostics {
+            listeners,
+            proxies: orphans.len() + self.metadata.len() + self.nodes.len() + self.streams.len(),
+            orphans: orphans.len(),
+            nodes: self.nodes.len(),
+            streams: self.streams.len(),
+            waiters: self.waiters.len(),
+            pending_requests: 0,
+        }
+    }
+
+    pub fn notify_waiters(&mut self) {
+        if self.waiters.is_empty() {
+            return;
+        }
+        let waiters = std::mem::take(&mut self.waiters);
+        for waiter in waiters {
+            match self.check_condition(&waiter.condition) {
+                Some(result) => (waiter.callback)(result),
+                None => self.waiters.push(waiter),
+            }
+        }
     }
 }
 
@@ -259,6 +450,10 @@ impl Default for GlobalState {
     fn default() -> Self {
         GlobalState {
             orphans: Rc::new(RefCell::new(HashMap::new())),
+            next_orphan_index: 0,
+            waiters: Vec::new(),
+            created_nodes: HashMap::new(),
+            client_nodes: HashSet::new(),
             clients: HashMap::new(),
             metadata: HashMap::new(),
             nodes: HashMap::new(),
@@ -271,7 +466,8 @@ impl Default for GlobalState {
 
 pub(super) struct OrphanState {
     proxy: pipewire::proxy::Proxy,
-    listeners: Rc<RefCell<Listeners<pipewire::proxy::ProxyListener>>>
+    listeners: Rc<RefCell<Listeners<pipewire::proxy::ProxyListener>>>,
+    is_removed: Rc<Cell<bool>>,
 }
 
 impl OrphanState {
@@ -279,24 +475,82 @@ impl OrphanState {
         Self {
             proxy,
             listeners: Rc::new(RefCell::new(Listeners::new())),
+            is_removed: Rc::new(Cell::new(false)),
         }
     }
 
+    pub(super) fn get_listener_names(&self) -> Vec<String> {
+        self.listeners.borrow().get_names()
+    }
+
+    pub(super) fn prune_listeners(&self) {
+        self.listeners.borrow_mut().prune();
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.is_removed.get()
+    }
+
     pub fn add_removed_listener<F>(&mut self, callback: F)
     where
         F: Fn(&mut ListenerControlFlow) + 'static
     {
         const LISTENER_NAME: &str = "removed";
-        let listeners = self.listeners.clone();
         let control_flow = Rc::new(RefCell::new(ListenerControlFlow::new()));
         let listener_control_flow = control_flow.clone();
+        let is_removed = self.is_removed.clone();
         let listener = self.proxy.add_listener_local()
             .removed(move || {
+                is_removed.set(true);
                 if listener_control_flow.borrow().is_released() {
                     return;
                 }
                 callback(&mut listener_control_flow.borrow_mut());
-                listeners.borrow_mut().triggered(&LISTENER_NAME.to_string());
+            })
+            .register();
+        self.listeners.borrow_mut().add(
+            LISTENER_NAME.to_string(),
+            Listener::new(listener, control_flow)
+        );
+    }
+
+    /// Server errors about this proxy (e.g. object creation rejected), core dispatches them
+    /// to the proxy whose id they carry
+    pub fn add_error_listener<F>(&mut self, callback: F)
+    where
+        F: Fn(&mut ListenerControlFlow, &str) + 'static
+    {
+        const LISTENER_NAME: &str = "error";
+        let control_flow = Rc::new(RefCell::new(ListenerControlFlow::new()));
+        let listener_control_flow = control_flow.clone();
+        let listener = self.proxy.add_listener_local()
+            .error(move |_, _, message| {
+                if listener_control_flow.borrow().is_released() {
+                    return;
+                }
+                callback(&mut listener_control_flow.borrow_mut(), message);
+            })
+            .register();
+        self.listeners.borrow_mut().add(
+            LISTENER_NAME.to_string(),
+            Listener::new(listener, control_flow)
+        );
+    }
+
+    /// Bound id is the global id of the object created through this proxy
+    pub fn add_bound_listener<F>(&mut self, callback: F)
+    where
+        F: Fn(&mut ListenerControlFlow, GlobalId) + 'static
+    {
+        const LISTENER_NAME: &str = "bound";
+        let control_flow = Rc::new(RefCell::new(ListenerControlFlow::new()));
+        let listener_control_flow = control_flow.clone();
+        let listener = self.proxy.add_listener_local()
+            .bound(move |id| {
+                if listener_control_flow.borrow().is_released() {
+                    return;
+                }
+                callback(&mut listener_control_flow.borrow_mut(), id.into());
             })
             .register();
         self.listeners.borrow_mut().add(
@@ -311,6 +565,10 @@ pub(super) struct NodeState {
     state: GlobalObjectState,
     properties: Option<HashMap<String, String>>,
     format: Option<AudioInfoRaw>,
+    format_error: Option<Error>,
+    status: NodeStatus,
+    max_input_ports: u32,
+    max_output_ports: u32,
     listeners: Rc<RefCell<Listeners<pipewire::node::NodeListener>>>
 }
 
@@ -321,6 +579,10 @@ impl NodeState {
             state: GlobalObjectState::Pending,
             properties: None,
             format: None,
+            format_error: None,
+            status: NodeStatus::Creating,
+            max_input_ports: 0,
+            max_output_ports: 0,
             listeners: Rc::new(RefCell::new(Listeners::new())),
         }
     }
@@ -328,13 +590,21 @@ impl NodeState {
     pub(super) fn get_listener_names(&self) -> Vec<String> {
         self.listeners.borrow().get_names()
     }
+
+    pub(super) fn prune_listeners(&self) {
+        self.listeners.borrow_mut().prune();
+    }
     
     pub fn state(&self) -> GlobalObjectState {
         self.state.clone()
     }
 
-    fn set_state(&mut self) {        
-        if self.properties.is_some() && self.format.is_some() {
+    fn set_state(&mut self) {
+        // Only audio sink/source nodes have their format retrieved
+        let is_format_ready = self.format.is_some() || self.direction().is_none();
+        if self.format_error.is_some() {
+            self.state = GlobalObjectState::Failed
+        } else if self.properties.is_some() && is_format_ready {
             self.state = GlobalObjectState::Initialized
         } else {
             self.state = GlobalObjectState::Pending
@@ -353,15 +623,144 @@ impl NodeState {
         self.set_state();
     }
     
+    pub fn has_properties(&self, properties: &HashMap<String, String>) -> bool {
+        let Some(node_properties) = self.properties.as_ref() else {
+            return false;
+        };
+        properties.iter().all(|(key, value)| node_properties.get(key) == Some(value))
+    }
+
+    /// Send properties to node through Props parameter (i.e. SPA_PROP_params). Local properties
+    /// are only updated once server reports them through node info.
+    pub fn update_properties(&self, properties: &HashMap<String, String>) -> Result<(), Error> {
+        let props = Props {
+            params: Some(
+                properties.iter()
+                    .map(move |(key, value)| (key.clone(), pipewire::spa::pod::Value::String(value.clone())))
+                    .collect()
+            ),
+            ..Props::default()
+        };
+        let values: Vec<u8> = pipewire::spa::pod::serialize::PodSerializer::serialize(
+            Cursor::new(Vec::new()),
+            &props,
+        )
+            .map_err(move |error| Error {
+                description: format!("Failed POD serialization for type(Props): {:?}", error)
+            })?
+            .0
+            .into_inner();
+        let parameter = match pipewire::spa::pod::Pod::from_bytes(&values) {
+            Some(value) => value,
+            None => return Err(Error {
+                description: "Failed to create POD for type(Props)".to_string()
+            })
+        };
+        self.proxy.set_param(pipewire::spa::param::ParamType::Props, 0, parameter);
+        Ok(())
+    }
+
+    /// Send node command (e.g. SPA_NODE_COMMAND_Suspend). Node proxy doesn't expose
+    /// pw_node_send_command, so we call node methods through proxy interface like
+    /// the C helper does.
+    pub fn send_command(&self, core: &pipewire::core::Core, command: u32) -> Result<(), Error> {
+        let command = pipewire::spa::sys::spa_command {
+            pod: pipewire::spa::sys::spa_pod {
+                size: std::mem::size_of::<pipewire::spa::sys::spa_command_body>() as u32,
+                type_: pipewire::spa::sys::SPA_TYPE_Object,
+            },
+            body: pipewire::spa::sys::spa_command_body {
+                body: pipewire::spa::sys::spa_pod_object_body {
+                    type_: pipewire::spa::sys::SPA_TYPE_COMMAND_Node,
+                    id: command,
+                },
+            },
+        };
+        let proxy_id = self.proxy.upcast_ref().id();
+        // SAFETY: proxy is owned by this state and alive during call. Node proxy
+        // starts with its spa_interface which callbacks implement pw_node_methods.
+        let result = unsafe {
+            let proxy = pipewire::sys::pw_core_find_proxy(core.as_raw_ptr(), proxy_id);
+            if proxy.is_null() {
+                return Err(Error {
+                    description: format!("Proxy with id({}) not found", proxy_id),
+                });
+            }
+            let interface = proxy as *mut pipewire::spa::sys::spa_interface;
+            let methods = (*interface).cb.funcs as *const pipewire::sys::pw_node_methods;
+            match (*methods).send_command {
+                Some(send_command) => send_command((*interface).cb.data, &command),
+                None => return Err(Error {
+                    description: "Node proxy doesn't implement send_command method".to_string(),
+                })
+            }
+        };
+        if result < 0 {
+            return Err(Error {
+                description: format!("Failed to send node command({}): {}", command.body.body.id, result),
+            });
+        }
+        Ok(())
+    }
+
     pub fn format(&self) -> Option<AudioInfoRaw> {
         self.format.clone()
     }
     
     pub fn set_format(&mut self, format: AudioInfoRaw) {
         self.format = Some(format);
+        self.format_error = None;
+        self.set_state();
+    }
+
+    pub fn format_error(&self) -> Option<Error> {
+        self.format_error.clone()
+    }
+
+    /// Node is left out of lookups and readiness until a valid format is received
+    pub fn set_format_error(&mut self, error: Error) {
+        self.format = None;
+        self.format_error = Some(error);
         self.set_state();
     }
     
+    pub fn status(&self) -> NodeStatus {
+        self.status.clone()
+    }
+
+    pub fn max_input_ports(&self) -> u32 {
+        self.max_input_ports
+    }
+
+    pub fn max_output_ports(&self) -> u32 {
+        self.max_output_ports
+    }
+
+    /// Ask server to destroy this node, lingering nodes are only destroyed this way
+    pub fn destroy(self, core: &pipewire::core::Core) -> Result<(), Error> {
+        core.destroy_object(self.proxy)
+            .map(|_| ())
+            .map_err(|error| Error {
+                description: format!("Failed to destroy node: {}", error),
+            })
+    }
+
+    /// Whether server keeps node once its creator is disconnected (i.e. object.linger)
+    pub fn is_lingering(&self) -> bool {
+        match &self.properties {
+            Some(properties) => properties
+                .get(*pipewire::keys::OBJECT_LINGER)
+                .is_some_and(|value| value == "true" || value == "1"),
+            None => false,
+        }
+    }
+
+    pub fn set_info(&mut self, status: NodeStatus, max_input_ports: u32, max_output_ports: u32) {
+        self.status = status;
+        self.max_input_ports = max_input_ports;
+        self.max_output_ports = max_output_ports;
+    }
+
     pub fn name(&self) -> Result<String, Error> {
         match self.properties.as_ref().unwrap().get(*pipewire::keys::NODE_NAME) {
             Some(value) => Ok(value.clone()),
@@ -371,14 +770,13 @@ impl NodeState {
         }
     }
     
-    pub fn direction(&self) -> Result<Direction, Error> {
-        let media_class = self.properties.as_ref().unwrap().get(*pipewire::keys::MEDIA_CLASS).unwrap().clone();
+    /// None when node is not an audio sink/source (e.g. video source, MIDI bridge)
+    pub fn direction(&self) -> Option<Direction> {
+        let media_class = self.properties.as_ref()?.get(*pipewire::keys::MEDIA_CLASS)?;
         match media_class.as_str() {
-            MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SOURCE => Ok(Direction::Input),
-            MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SINK => Ok(Direction::Output),
-            _ => Err(Error {
-                description: "Media class not an audio sink/source".to_string(),
-            })
+            MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SOURCE => Some(Direction::Input),
+            MEDIA_CLASS_PROPERTY_VALUE_AUDIO_SINK => Some(Direction::Output),
+            _ => None
         }
     }
 
@@ -386,8 +784,6 @@ impl NodeState {
     where
         F: Fn(&mut ListenerControlFlow, &pipewire::node::NodeInfoRef) + 'static
     {
-        let listeners = self.listeners.clone();
-        let listener_name = name.clone();
         let control_flow = Rc::new(RefCell::new(ListenerControlFlow::new()));
         let listener_control_flow = control_flow.clone();
         let listener = self.proxy.add_listener_local()
@@ -396,7 +792,6 @@ impl NodeState {
                     return;
                 }
                 listener(&mut listener_control_flow.borrow_mut(), info);
-                listeners.borrow_mut().triggered(&listener_name);
             })
             .register();
         self.listeners.borrow_mut().add(name, Listener::new(listener, control_flow));
@@ -419,6 +814,30 @@ impl NodeState {
         );
     }
 
+    /// Info listener is never released, node state and properties can change
+    /// during node lifetime (e.g. idle -> running).
+    pub fn add_details_listener<F>(&mut self, callback: F)
+    where
+        F: Fn(&mut ListenerControlFlow, NodeStatus, u32, u32, Option<HashMap<String, String>>) + 'static,
+    {
+        self.add_info_listener(
+            "info".to_string(),
+            move |control_flow, info| {
+                let properties = match info.change_mask().contains(pipewire::node::NodeChangeMask::PROPS) {
+                    true => info.props().map(dict_ref_to_hashmap),
+                    false => None
+                };
+                callback(
+                    control_flow,
+                    info.state().into(),
+                    info.max_input_ports(),
+                    info.max_output_ports(),
+                    properties
+                );
+            }
+        );
+    }
+
     fn add_parameter_listener<F>(
         &mut self,
         name: String,
@@ -428,8 +847,6 @@ impl NodeState {
     where
         F: Fn(&mut ListenerControlFlow, &pipewire::spa::pod::Pod) + 'static,
     {
-        let listeners = self.listeners.clone();
-        let listener_name = name.clone();
         let control_flow = Rc::new(RefCell::new(ListenerControlFlow::new()));
         let listener_control_flow = control_flow.clone();
         self.proxy.subscribe_params(&[expected_kind]);
@@ -446,7 +863,6 @@ impl NodeState {
                     return;
                 };
                 listener(&mut listener_control_flow.borrow_mut(), parameter);
-                listeners.borrow_mut().triggered(&listener_name);
             })
             .register();
         self.listeners.borrow_mut().add(name, Listener::new(listener, control_flow));
@@ -460,9 +876,16 @@ impl NodeState {
             "format".to_string(),
             pipewire::spa::param::ParamType::EnumFormat,
             move |control_flow, parameter| {
-                let (media_type, media_subtype): (MediaType, MediaSubtype) =
+                // Media types unknown to spa-utils are skipped like any other non audio format
+                let (media_type, media_subtype) =
                     match pipewire::spa::param::format_utils::parse_format(parameter) {
-                        Ok((media_type, media_subtype)) => (media_type.0.into(), media_subtype.0.into()),
+                        Ok((media_type, media_subtype)) => match (
+                            MediaType::try_from(media_type.0),
+                            MediaSubtype::try_from(media_subtype.0)
+                        ) {
+                            (Ok(media_type), Ok(media_subtype)) => (media_type, media_subtype),
+                            _ => return,
+                        },
                         Err(_) => return,
                     };
                 let pod = parameter;
@@ -470,32 +893,21 @@ impl NodeState {
                 let parameter = match media_type {
                     MediaType::Audio => match media_subtype {
                         MediaSubtype::Raw => {
-                            let result = pipewire::spa::pod::deserialize::PodDeserializer::deserialize_from(data);
-                            let result = result
-                                .map(move |(_, parameter)| {
-                                    parameter
+                            // Detailed error is lost once converted into libspa DeserializeError,
+                            // deserialize as value first to keep it
+                            let value = match pipewire::spa::pod::deserialize::PodDeserializer::deserialize_any_from(data) {
+                                Ok((_, value)) => value,
+                                Err(_) => return callback(control_flow, Err(Error {
+                                    description: "Failed POD deserialization for type(AudioInfoRaw): Parsing error".to_string(),
+                                })),
+                            };
+                            AudioInfoRaw::try_from(value)
+                                .map_err(|error| Error {
+                                    description: format!(
+                                        "Failed POD deserialization for type(AudioInfoRaw): {}",
+                                        error
+                                    ),
                                 })
-                                .map_err(move |error| {
-                                    let description = match error {
-                                        pipewire::spa::pod::deserialize::DeserializeError::Nom(_) => "Parsing error",
-                                        pipewire::spa::pod::deserialize::DeserializeError::UnsupportedType => "Unsupported type",
-                                        pipewire::spa::pod::deserialize::DeserializeError::InvalidType => "Invalid type",
-                                        pipewire::spa::pod::deserialize::DeserializeError::PropertyMissing => "Property missing",
-                                        pipewire::spa::pod::deserialize::DeserializeError::PropertyWrongKey(value) => &*format!(
-                                            "Wrong property key({})", 
-                                            value
-                                        ),
-                                        pipewire::spa::pod::deserialize::DeserializeError::InvalidChoiceType => "Invalide choice type",
-                                        pipewire::spa::pod::deserialize::DeserializeError::MissingChoiceValues => "Missing choice values",
-                                    };
-                                    Error {
-                                        description: format!(
-                                            "Failed POD deserialization for type(AudioInfoRaw): {}", 
-                                            description
-                                        ),
-                                    }
-                                });
-                            result
                         }
                         _ => return
                     },
@@ -540,12 +952,15 @@ impl MetadataState {
         self.listeners.borrow().get_names()
     }
 
+    pub(super) fn prune_listeners(&self) {
+        self.listeners.borrow_mut().prune();
+    }
+
     pub fn add_property_listener<F>(&mut self, listener: F)
     where
         F: Fn(&mut ListenerControlFlow, u32, Option<&str>, Option<&str>, Option<&str>) -> i32 + Sized + 'static
     {
         const LISTENER_NAME: &str = "property";
-        let listeners = self.listeners.clone();
         let control_flow = Rc::new(RefCell::new(ListenerControlFlow::new()));
         let listener_control_flow = control_flow.clone();
         let listener = self.proxy.add_listener_local()
@@ -560,7 +975,6 @@ impl MetadataState {
                     kind, 
                     value
                 );
-                listeners.borrow_mut().triggered(&LISTENER_NAME.to_string());
                 result
             })
             .register();
@@ -578,7 +992,8 @@ pub(super) struct StreamState {
     proxy: pipewire::stream::Stream,
     pub(super) name: String,
     is_connected: bool,
-    format: pipewire::spa::param::audio::AudioInfoRaw,
+    format: StreamInfo,
+    negotiated_format: Rc<RefCell<Option<StreamInfo>>>,
     direction: pipewire::spa::utils::Direction,
     listeners: Rc<RefCell<Listeners<pipewire::stream::StreamListener<StreamUserData>>>>,
 }
@@ -586,7 +1001,7 @@ pub(super) struct StreamState {
 impl StreamState {
     pub fn new(
         name: String,
-        format: pipewire::spa::param::audio::AudioInfoRaw,
+        format: StreamInfo,
         direction: pipewire::spa::utils::Direction,
         proxy: pipewire::stream::Stream
     ) -> Self {
@@ -595,6 +1010,7 @@ impl StreamState {
             proxy,
             is_connected: false,
             format,
+            negotiated_format: Rc::new(RefCell::new(None)),
             direction,
             listeners: Rc::new(RefCell::new(Listeners::new())),
         }
@@ -604,6 +1020,10 @@ impl StreamState {
         self.listeners.borrow().get_names()
     }
 
+    pub(super) fn prune_listeners(&self) {
+        self.listeners.borrow_mut().prune();
+    }
+
     pub fn is_connected(&self) -> bool {
         self.is_connected
     }
@@ -614,19 +1034,22 @@ impl StreamState {
                 description: format!("Stream {} is already connected", self.name)
             });
         }
-        let object = pipewire::spa::pod::Value::Object(pipewire::spa::pod::Object {
-            type_: pipewire::spa::sys::SPA_TYPE_OBJECT_Format,
-            id: pipewire::spa::sys::SPA_PARAM_EnumFormat,
-            properties: self.format.into(),
-        });
         let values: Vec<u8> = pipewire::spa::pod::serialize::PodSerializer::serialize(
             Cursor::new(Vec::new()),
-            &object,
+            &self.format,
         )
-        .unwrap()
-        .0
-        .into_inner();
-        let mut params = [pipewire::spa::pod::Pod::from_bytes(&values).unwrap()];
+            .map_err(move |error| Error {
+                description: format!("Failed POD serialization for type(StreamInfo): {:?}", error)
+            })?
+            .0
+            .into_inner();
+        let parameter = match pipewire::spa::pod::Pod::from_bytes(&values) {
+            Some(value) => value,
+            None => return Err(Error {
+                description: "Failed to create POD for type(StreamInfo)".to_string()
+            })
+        };
+        let mut params = [parameter];
         let flags = pipewire::stream::StreamFlags::AUTOCONNECT | pipewire::stream::StreamFlags::MAP_BUFFERS;
         self.proxy
             .connect(
@@ -650,16 +1073,89 @@ impl StreamState {
             .disconnect()
             .map_err(move |error| Error { description: error.to_string() })?;
         self.is_connected = false;
+        self.negotiated_format.replace(None);
         Ok(())
     }
 
+    /// Shared with waiters, they are called while global state is locked
+    pub fn negotiated_format(&self) -> Rc<RefCell<Option<StreamInfo>>> {
+        self.negotiated_format.clone()
+    }
+
+    pub fn set_negotiated_format(&mut self, format: StreamInfo) {
+        self.negotiated_format.replace(Some(format));
+    }
+
+    /// Format listener is never released, format is negotiated again on each
+    /// connection or when peer changes it.
+    pub fn add_format_listener<F>(&mut self, callback: F)
+    where
+        F: Fn(&mut ListenerControlFlow, Result<StreamInfo, Error>) + 'static,
+    {
+        const LISTENER_NAME: &str = "format";
+        let control_flow = Rc::new(RefCell::new(ListenerControlFlow::new()));
+        let listener_control_flow = control_flow.clone();
+        let listener = self.proxy.add_local_listener()
+            .param_changed(move |_, _, id, parameter| {
+                if listener_control_flow.borrow().is_released() {
+                    return;
+                }
+                if id != pipewire::spa::param::ParamType::Format.as_raw() {
+                    return;
+                }
+                // Format is cleared when stream is disconnected
+                let Some(parameter) = parameter else {
+                    return;
+                };
+                let (media_type, media_subtype) =
+                    match pipewire::spa::param::format_utils::parse_format(parameter) {
+                        Ok((media_type, media_subtype)) => match (
+                            MediaType::try_from(media_type.0),
+                            MediaSubtype::try_from(media_subtype.0)
+                        ) {
+                            (Ok(media_type), Ok(media_subtype)) => (media_type, media_subtype),
+                            _ => return,
+                        },
+                        Err(_) => return,
+                    };
+                let value = match pipewire::spa::pod::deserialize::PodDeserializer::deserialize_any_from(parameter.as_bytes()) {
+                    Ok((_, value)) => value,
+                    Err(_) => return callback(&mut listener_control_flow.borrow_mut(), Err(Error {
+                        description: "Failed POD deserialization for type(StreamInfo): Parsing error".to_string(),
+                    })),
+                };
+                let format = match (media_type, media_subtype) {
+                    (MediaType::Audio, MediaSubtype::Raw) => AudioInfoRaw::try_from(value)
+                        .map(|value| StreamInfo::Audio(value.into())),
+                    (MediaType::Audio, MediaSubtype::Iec958) => AudioInfoIec958::try_from(value)
+                        .map(|value| StreamInfo::Audio(value.into())),
+                    (MediaType::Audio, MediaSubtype::Dsd) => AudioInfoDsd::try_from(value)
+                        .map(|value| StreamInfo::Audio(value.into())),
+                    (MediaType::Video, MediaSubtype::Raw) => VideoInfoRaw::try_from(value)
+                        .map(|value| StreamInfo::Video(value.into())),
+                    (MediaType::Video, MediaSubtype::Dsp) => VideoInfoDsp::try_from(value)
+                        .map(|value| StreamInfo::Video(value.into())),
+                    (MediaType::Application, MediaSubtype::Control) => ControlInfo::try_from(value)
+                        .map(StreamInfo::Midi),
+                    // Compressed audio formats are passed through as is, nothing to surface
+                    _ => return,
+                };
+                let format = format.map_err(|error| Error {
+                    description: format!("Failed POD deserialization for type(StreamInfo): {}", error),
+                });
+                callback(&mut listener_control_flow.borrow_mut(), format);
+            })
+            .register()
+            .unwrap();
+        self.listeners.borrow_mut().add(LISTENER_NAME.to_string(), Listener::new(listener, control_flow));
+    }
+
     pub fn add_process_listener(
         &mut self,
         mut callback: StreamCallback
     )
     {
         const LISTENER_NAME: &str = "process";
-        let listeners = self.listeners.clone();
         let control_flow = Rc::new(RefCell::new(ListenerControlFlow::new()));
         let listener_control_flow = control_flow.clone();
         let listener = self.proxy.add_local_listener()
@@ -669,7 +1165,6 @@ impl StreamState {
                 }
                 let buffer = stream.dequeue_buffer().unwrap();
                 callback.call(&mut listener_control_flow.borrow_mut(), buffer);
-                listeners.borrow_mut().triggered(&LISTENER_NAME.to_string());
             })
             .register()
             .unwrap();
@@ -699,7 +1194,7 @@ impl From<&pipewire::spa::utils::dict::DictRef> for PortStateProperties {
         let group = properties.get("port.group").unwrap().to_string();
         Self {
             path,
-            channel: AudioChannel::UNKNOWN,
+            channel: AudioChannel::from_str(&channel).unwrap_or(AudioChannel::UNKNOWN),
             id: id.into(),
             name,
             direction: match direction.as_str() {
@@ -770,7 +1265,8 @@ impl SettingsState {
         const EXPECTED_PROPERTY: u32 = 5;
         let property_count: Rc<Cell<u32>> = Rc::new(Cell::new(0));
         move |control_flow, _, key, _, value| {
-            let settings = &mut state.lock().unwrap().settings;
+            let mut state = state.lock().unwrap();
+            let settings = &mut state.settings;
             let key = key.unwrap();
             let value = value.unwrap();
             match key {
@@ -803,6 +1299,7 @@ impl SettingsState {
             if let (GlobalObjectState::Pending, EXPECTED_PROPERTY) = (settings.state.clone(), property_count.get()) {
                 settings.state = GlobalObjectState::Initialized;
                 control_flow.release();
+                state.notify_waiters();
             }
             0
         }
@@ -832,7 +1329,8 @@ impl DefaultAudioNodesState {
         const EXPECTED_PROPERTY: u32 = 2;
         let property_count: Rc<Cell<u32>> = Rc::new(Cell::new(0));
         move |control_flow, _, key, _, value| {
-            let default_audio_devices = &mut state.lock().unwrap().default_audio_nodes;
+            let mut state = state.lock().unwrap();
+            let default_audio_devices = &mut state.default_audio_nodes;
             let key = key.unwrap();
             if value.is_none() {
                 return 0;
@@ -866,6 +1364,7 @@ impl DefaultAudioNodesState {
             if let (GlobalObjectState::Pending, EXPECTED_PROPERTY) = (default_audio_devices.state.clone(), property_count.get()) {
                 default_audio_devices.state = GlobalObjectState::Initialized;
                 control_flow.release();
+                state.notify_waiters();
             }
             0
         }
@@ -9,13 +9,14 @@ use crate::listeners::PipewireCoreSync;
 impl CoreApi {
     pub(crate) fn get_listeners(&self) -> Result<HashMap<TypeId, HashMap<String, Vec<String>>>, Error> {
         let request = MessageRequest::Listeners;
-        let response = self.api.send_request(&request);
+        let response = self.api.send_request(&request, &self.options);
         match response {
             Ok(MessageResponse::Listeners {
                    core,
                    metadata,
                    nodes,
-                   streams
+                   streams,
+                   ..
                }) => {
                 let mut map = HashMap::new();
                 map.insert(TypeId::of::<PipewireCoreSync>(), core);
@@ -30,4 +31,28 @@ impl CoreApi {
             }),
         }
     }
+
+    pub(crate) fn provoke_core_error(&self) -> Result<(), Error> {
+        let request = MessageRequest::ProvokeCoreError;
+        let response = self.api.send_request(&request, &self.options);
+        match response {
+            Ok(MessageResponse::ProvokeCoreError) => Ok(()),
+            Err(value) => Err(value),
+            Ok(value) => Err(Error {
+                description: format!("Received unexpected response: {:?}", value),
+            }),
+        }
+    }
+
+    pub(crate) fn provoke_node_format_error(&self, id: u32) -> Result<(), Error> {
+        let request = MessageRequest::ProvokeNodeFormatError(id.into());
+        let response = self.api.send_request(&request, &self.options);
+        match response {
+            Ok(MessageResponse::ProvokeNodeFormatError) => Ok(()),
+            Err(value) => Err(value),
+            Ok(value) => Err(Error {
+                description: format!("Received unexpected response: {:?}", value),
+            }),
+        }
+    }
 }
\ No newline at end of file
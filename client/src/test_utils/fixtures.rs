@@ -1,7 +1,7 @@
 use std::any::TypeId;
 use std::collections::hash_map::Iter;
 use std::collections::HashMap;
-use crate::{NodeInfo, PipewireClient};
+use crate::{NodeCreateOptions, NodeInfo, PipewireClient};
 use pipewire_common::utils::Direction;
 use pipewire_test_utils::server::{server_with_default_configuration, server_without_node, server_without_session_manager, Server};
 use rstest::{fixture, Context};
@@ -46,15 +46,14 @@ impl Deref for NodeInfoFixture {
         let node = self.node.get_or_init(|| {
             let node_name = Uuid::new_v4().to_string();
             self.client.node()
-                .create(
+                .create(NodeCreateOptions::from_channels_count(
                     node_name.clone(),
                     node_name.clone(),
                     node_name.clone(),
                     self.direction.clone(),
                     2
-                ).unwrap();
-            let node = self.client.node().get(node_name, self.direction.clone()).unwrap();
-            node
+                ).unwrap())
+                .unwrap()
         });
         node
     }
@@ -77,7 +76,7 @@ pub struct StreamFixture {
 
 impl StreamFixture {
     pub(self) fn new(client: Arc<PipewireClient>, node: NodeInfoFixture) -> Self {
-        let direction = node.direction.clone();
+        let direction = node.direction.clone().unwrap();
         Self {
             client: client.clone(),
             node,
@@ -131,7 +130,7 @@ impl Deref for StreamFixture {
                 .create(
                     self.node.id,
                     self.direction.clone(),
-                    self.node.format.clone().into(),
+                    self.node.format.clone().unwrap().into(),
                     move |control_flow, _| {
                         assert!(true);
                         control_flow.release();
@@ -1,9 +1,11 @@
-use crate::build_modules::syntax::generators::enumerator::{EnumInfo, EnumVariantInfo};
+use crate::build_modules::syntax::generators::enumerator::{EnumAliasInfo, EnumInfo, EnumVariantInfo};
 use crate::build_modules::syntax::parsers::{StructImplVisitor, StructVisitor};
 use crate::build_modules::syntax::utils::AttributeExt;
 use crate::build_modules::utils::read_source_file;
 use indexmap::IndexMap;
 use itertools::Itertools;
+use quote::__private::TokenStream;
+use quote::{quote, ToTokens};
 use std::cmp::Ordering;
 use std::path::PathBuf;
 use syn::__private::quote::__private::ext::RepToTokensExt;
@@ -48,20 +50,68 @@ pub fn generate_enums(src_path: &PathBuf, build_path: &PathBuf, features: &Vec<S
         media_subtype_enum_info
     ];
 
-    generate_enums_code(enum_infos, "format.rs");
+    generate_enums_code(enum_infos, vec![], "format.rs");
 
     let file_path = PathBuf::from(&"bindings.rs");
     let src = read_source_file(&build_path, &file_path);
 
     let audio_sample_format_enum_info = map_audio_sample_format_enum_info(&src.items);
+    let audio_sample_format_metadata_code = generate_audio_sample_format_metadata_code(&audio_sample_format_enum_info);
     let audio_channel_enum_info = map_audio_channel_enum_info(&src.items);
+    let audio_iec958_codec_enum_info = map_prefixed_enum_info(
+        &src.items,
+        "SPA_AUDIO_IEC958_CODEC_",
+        "AudioIec958Codec",
+        "spa_audio_iec958_codec",
+        None
+    );
+    let audio_bitorder_enum_info = map_prefixed_enum_info(
+        &src.items,
+        "SPA_PARAM_BITORDER_",
+        "AudioBitorder",
+        "spa_param_bitorder",
+        None
+    );
+    let audio_aac_stream_format_enum_info = map_prefixed_enum_info(
+        &src.items,
+        "SPA_AUDIO_AAC_STREAM_FORMAT_",
+        "AudioAacStreamFormat",
+        "spa_audio_aac_stream_format",
+        None
+    );
 
     let enum_infos = vec![
         audio_sample_format_enum_info,
-        audio_channel_enum_info
+        audio_channel_enum_info,
+        audio_iec958_codec_enum_info,
+        audio_bitorder_enum_info,
+        audio_aac_stream_format_enum_info
+    ];
+
+    generate_enums_code(enum_infos, vec![audio_sample_format_metadata_code], "audio.rs");
+
+    let param_type_enum_info = map_param_type_enum_info(&src.items);
+
+    let enum_infos = vec![
+        param_type_enum_info
+    ];
+
+    generate_enums_code(enum_infos, vec![], "param.rs");
+
+    // DSP formats share their value with another format
+    let video_format_enum_info = map_prefixed_enum_info(
+        &src.items,
+        "SPA_VIDEO_FORMAT_",
+        "VideoFormat",
+        "spa_video_format",
+        Some(|suffix| suffix.starts_with("DSP_"))
+    );
+
+    let enum_infos = vec![
+        video_format_enum_info
     ];
 
-    generate_enums_code(enum_infos, "audio.rs");
+    generate_enums_code(enum_infos, vec![], "video.rs");
 }
 
 fn map_media_type_enum_info(items: &Vec<Item>) -> EnumInfo {
@@ -104,6 +154,7 @@ fn map_media_type_enum_info(items: &Vec<Item>) -> EnumInfo {
                     (index, variant)
                 })
                 .collect::<IndexMap<_, _>>(),
+        aliases: vec![],
     }
 }
 
@@ -160,63 +211,42 @@ where
                 (index, variant)
             })
             .collect::<IndexMap<_, _>>(),
+        aliases: vec![],
     }
 }
 
-fn spa_audio_format_idents() -> Vec<String> {
-    let audio_formats: Vec<String> = vec![
-        "S8".to_string(),
-        "U8".to_string(),
-        "S16".to_string(),
-        "U16".to_string(),
-        "S24".to_string(),
-        "U24".to_string(),
-        "S24_32".to_string(),
-        "U24_32".to_string(),
-        "S32".to_string(),
-        "U32".to_string(),
-        "F32".to_string(),
-        "F64".to_string(),
-    ];
-
-    let ends = vec![
-        "LE".to_string(),
-        "BE".to_string(),
-        "P".to_string(),
-    ];
-
-    audio_formats.iter()
-        .flat_map(move |format| {
-            ends.iter()
-                .map(move |end| {
-                    if format.contains("8") && end != "P" {
-                        format!("SPA_AUDIO_FORMAT_{}", format)
-                    }
-                    else if format.contains("8") && end == "P" {
-                        format!("SPA_AUDIO_FORMAT_{}{}", format, end)
-                    }
-                    else if format.contains("8") == false && end == "P" {
-                        format!("SPA_AUDIO_FORMAT_{}{}", format, end)
-                    }
-                    else {
-                        format!("SPA_AUDIO_FORMAT_{}_{}", format, end)
-                    }
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect()
-}
-
 fn map_audio_sample_format_enum_info(items: &Vec<Item>) -> EnumInfo {
-    let spa_audio_format_idents = spa_audio_format_idents();
+    const PREFIX: &str = "SPA_AUDIO_FORMAT_";
+
     let constants = map_constant_info(
         &items,
-        move |constant| spa_audio_format_idents.contains(constant),
+        move |constant| {
+            if constant.starts_with(PREFIX) == false {
+                return false;
+            }
+
+            let constant = constant.replace(PREFIX, "");
+
+            // Range markers (i.e. START_Interleaved) are not formats
+            constant.starts_with("START") == false
+        },
         move |a, b| {
             a.cmp(&b)
         }
     );
 
+    let names = constants.iter()
+        .map(|constant| constant.ident.to_string().replace(PREFIX, ""))
+        .collect::<Vec<_>>();
+    // DSP, native and opposite endian formats share their value with another format
+    let is_alias = move |name: &String| {
+        name.starts_with("DSP_")
+            || name.ends_with("_OE")
+            || names.contains(&format!("{}_LE", name))
+    };
+    let (aliases, constants): (Vec<_>, Vec<_>) = constants.into_iter()
+        .partition(|constant| is_alias(&constant.ident.to_string().replace(PREFIX, "")));
+
     let ident = "AudioSampleFormat";
     let spa_type = "spa_audio_format";
     
@@ -231,7 +261,7 @@ fn map_audio_sample_format_enum_info(items: &Vec<Item>) -> EnumInfo {
         variants: constants.iter()
             .map(move |constant| {
                 let index = constant.ident.to_string();
-                let ident = constant.ident.to_string().replace("SPA_AUDIO_FORMAT_", "");
+                let ident = constant.ident.to_string().replace(PREFIX, "");
                 let ident = Ident::new(&ident, ident.span());
                 let discriminant = *constant.expr.clone();
                 let variant = EnumVariantInfo {
@@ -243,9 +273,151 @@ fn map_audio_sample_format_enum_info(items: &Vec<Item>) -> EnumInfo {
                 (index, variant)
             })
             .collect::<IndexMap<_, _>>(),
+        aliases: aliases.iter()
+            .filter_map(|alias| {
+                let discriminant = alias.expr.to_token_stream().to_string();
+                let target = constants.iter()
+                    .find(|constant| constant.expr.to_token_stream().to_string() == discriminant)?;
+                let ident = alias.ident.to_string().replace(PREFIX, "");
+                let target = target.ident.to_string().replace(PREFIX, "");
+                Some(EnumAliasInfo {
+                    ident: Ident::new(&ident, ident.span()),
+                    target: Ident::new(&target, target.span()),
+                })
+            })
+            .collect(),
     }
 }
 
+fn generate_audio_sample_format_metadata_code(enum_info: &EnumInfo) -> String {
+    let metadata = enum_info.variants.values()
+        .map(|variant| {
+            let ident = variant.ident.to_string();
+            let is_planar = ident.ends_with('P');
+            let is_float = ident.starts_with('F');
+            let (base, endianness) = if let Some(base) = ident.strip_suffix("_LE") {
+                (base, "Little")
+            } else if let Some(base) = ident.strip_suffix("_BE") {
+                (base, "Big")
+            } else if is_planar {
+                (&ident[..ident.len() - 1], "NATIVE")
+            } else {
+                (ident.as_str(), "")
+            };
+            // S20 and S18 are stored on three bytes as S24
+            let bytes_per_sample: u32 = match base {
+                "S8" | "U8" | "ULAW" | "ALAW" => 1,
+                "S16" | "U16" => 2,
+                "S24" | "U24" | "S20" | "U20" | "S18" | "U18" => 3,
+                "S24_32" | "U24_32" | "S32" | "U32" | "F32" => 4,
+                "F64" => 8,
+                _ => 0,
+            };
+            // SPA short names do not separate endianness suffix (i.e. S16_LE => S16LE)
+            let spa_name = ident.replace("_LE", "LE").replace("_BE", "BE");
+            let endianness = match bytes_per_sample > 1 && endianness.is_empty() == false {
+                true => {
+                    let endianness = Ident::new(endianness, endianness.span());
+                    quote! { Some(AudioSampleEndianness::#endianness) }
+                }
+                false => quote! { None },
+            };
+            (variant, bytes_per_sample, is_planar, is_float, endianness, spa_name)
+        })
+        .collect::<Vec<_>>();
+    let bytes_per_sample_quote = metadata.iter()
+        .map(|(variant, bytes_per_sample, _, _, _, _)| {
+            let ident = variant.ident.to_token_stream();
+            let attributes = variant.attributes.to_token_stream();
+            quote! {
+                #attributes
+                Self::#ident => #bytes_per_sample,
+            }
+        })
+        .collect::<TokenStream>();
+    let is_planar_quote = metadata.iter()
+        .map(|(variant, _, is_planar, _, _, _)| {
+            let ident = variant.ident.to_token_stream();
+            let attributes = variant.attributes.to_token_stream();
+            quote! {
+                #attributes
+                Self::#ident => #is_planar,
+            }
+        })
+        .collect::<TokenStream>();
+    let is_float_quote = metadata.iter()
+        .map(|(variant, _, _, is_float, _, _)| {
+            let ident = variant.ident.to_token_stream();
+            let attributes = variant.attributes.to_token_stream();
+            quote! {
+                #attributes
+                Self::#ident => #is_float,
+            }
+        })
+        .collect::<TokenStream>();
+    let endianness_quote = metadata.iter()
+        .map(|(variant, _, _, _, endianness, _)| {
+            let ident = variant.ident.to_token_stream();
+            let attributes = variant.attributes.to_token_stream();
+            quote! {
+                #attributes
+                Self::#ident => #endianness,
+            }
+        })
+        .collect::<TokenStream>();
+    let spa_name_quote = metadata.iter()
+        .map(|(variant, _, _, _, _, spa_name)| {
+            let ident = variant.ident.to_token_stream();
+            let attributes = variant.attributes.to_token_stream();
+            quote! {
+                #attributes
+                Self::#ident => #spa_name,
+            }
+        })
+        .collect::<TokenStream>();
+    let item_ident_quote = enum_info.ident.to_token_stream();
+    let attributes_quote = enum_info.attributes.to_token_stream();
+    let code = quote! {
+        #attributes_quote
+        impl #item_ident_quote {
+            /// Size of a single sample, zero for unknown and encoded formats
+            pub fn bytes_per_sample(&self) -> u32 {
+                match self {
+                    #bytes_per_sample_quote
+                }
+            }
+
+            pub fn is_planar(&self) -> bool {
+                match self {
+                    #is_planar_quote
+                }
+            }
+
+            pub fn is_float(&self) -> bool {
+                match self {
+                    #is_float_quote
+                }
+            }
+
+            /// Single byte, compressed and encoded formats have no endianness
+            pub fn endianness(&self) -> Option<AudioSampleEndianness> {
+                match self {
+                    #endianness_quote
+                }
+            }
+
+            /// Name used by PipeWire properties (e.g. audio.format = S16LE)
+            pub fn spa_name(&self) -> &'static str {
+                match self {
+                    #spa_name_quote
+                }
+            }
+        }
+    };
+    let file = syn::parse_file(code.to_string().as_str()).unwrap();
+    prettyplease::unparse(&file)
+}
+
 fn map_audio_channel_enum_info(items: &Vec<Item>) -> EnumInfo {
     let constants = map_constant_info(
         &items,
@@ -256,7 +428,8 @@ fn map_audio_channel_enum_info(items: &Vec<Item>) -> EnumInfo {
             
             let constant = constant.replace("SPA_AUDIO_CHANNEL_", "");
             
-            if constant.starts_with("START") || constant.starts_with("LAST") || constant.starts_with("AUX") {
+            // Range markers are not channels, START_Aux also shares its value with AUX0
+            if constant.starts_with("START") || constant.starts_with("LAST") {
                 return false;
             }
             
@@ -293,6 +466,115 @@ fn map_audio_channel_enum_info(items: &Vec<Item>) -> EnumInfo {
                 (index, variant)
             })
             .collect::<IndexMap<_, _>>(),
+        aliases: vec![],
+    }
+}
+
+fn map_param_type_enum_info(items: &Vec<Item>) -> EnumInfo {
+    const PREFIX: &str = "SPA_PARAM_";
+
+    let constants = map_constant_info(
+        &items,
+        move |constant| {
+            if constant.starts_with(PREFIX) == false {
+                return false;
+            }
+
+            // Object keys and flags are prefixed by their object name (i.e. SPA_PARAM_ROUTE_index)
+            constant.replace(PREFIX, "").contains("_") == false
+        },
+        move |a, b| {
+            a.cmp(&b)
+        }
+    );
+
+    let ident = "ParamType";
+    let spa_type = "spa_param_type";
+
+    EnumInfo {
+        ident: Ident::new(ident, ident.span()),
+        attributes: vec![],
+        spa_type: Ident::new(spa_type, spa_type.span()),
+        representation_type: "u32".to_string(),
+        variants: constants.iter()
+            .map(move |constant| {
+                let index = constant.ident.to_string();
+                let ident = constant.ident.to_string().replace(PREFIX, "");
+                let ident = Ident::new(&ident, ident.span());
+                let discriminant = *constant.expr.clone();
+                let variant = EnumVariantInfo {
+                    attributes: constant.attrs.clone(),
+                    fields: Fields::Unit,
+                    ident,
+                    discriminant,
+                };
+                (index, variant)
+            })
+            .collect::<IndexMap<_, _>>(),
+        aliases: vec![],
+    }
+}
+
+/// Enum from every constant sharing a prefix, variants keep constant suffix (e.g. AC3).
+/// Constants matched by is_alias share their value with another constant, they are
+/// generated as aliases of it.
+fn map_prefixed_enum_info(
+    items: &Vec<Item>,
+    prefix: &str,
+    ident: &str,
+    spa_type: &str,
+    is_alias: Option<fn(&str) -> bool>
+) -> EnumInfo {
+    let constants = map_constant_info(
+        &items,
+        move |constant| constant.starts_with(prefix) && constant.ends_with("ENUM_LAST") == false,
+        move |a, b| {
+            a.cmp(&b)
+        }
+    );
+
+    let (aliases, constants): (Vec<_>, Vec<_>) = constants.into_iter()
+        .partition(|constant| {
+            is_alias.is_some_and(|is_alias| is_alias(&constant.ident.to_string().replace(prefix, "")))
+        });
+
+    let mut attributes: Vec<Attribute> = vec![];
+    attributes.push_one("allow", "non_camel_case_types");
+    attributes.push_one("allow", "unused_doc_comments");
+
+    EnumInfo {
+        ident: Ident::new(ident, ident.span()),
+        attributes,
+        spa_type: Ident::new(spa_type, spa_type.span()),
+        representation_type: "u32".to_string(),
+        variants: constants.iter()
+            .map(move |constant| {
+                let index = constant.ident.to_string();
+                let ident = constant.ident.to_string().replace(prefix, "");
+                let ident = Ident::new(&ident, ident.span());
+                let discriminant = *constant.expr.clone();
+                let variant = EnumVariantInfo {
+                    attributes: constant.attrs.clone(),
+                    fields: Fields::Unit,
+                    ident,
+                    discriminant,
+                };
+                (index, variant)
+            })
+            .collect::<IndexMap<_, _>>(),
+        aliases: aliases.iter()
+            .filter_map(|alias| {
+                let discriminant = alias.expr.to_token_stream().to_string();
+                let target = constants.iter()
+                    .find(|constant| constant.expr.to_token_stream().to_string() == discriminant)?;
+                let ident = alias.ident.to_string().replace(prefix, "");
+                let target = target.ident.to_string().replace(prefix, "");
+                Some(EnumAliasInfo {
+                    ident: Ident::new(&ident, ident.span()),
+                    target: Ident::new(&target, target.span()),
+                })
+            })
+            .collect(),
     }
 }
 
@@ -429,9 +711,10 @@ where
         .unwrap()
 }
 
-fn generate_enums_code(enums: Vec<EnumInfo>, filename: &str) {
+fn generate_enums_code(enums: Vec<EnumInfo>, extra_codes: Vec<String>, filename: &str) {
     let code = enums.iter()
         .map(move |enum_info| enum_info.generate())
+        .chain(extra_codes)
         .collect::<Vec<_>>()
         .join("\n");
 
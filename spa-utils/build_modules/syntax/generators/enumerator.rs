@@ -14,7 +14,8 @@ pub struct EnumInfo {
     pub attributes: Vec<Attribute>,
     pub spa_type: Ident,
     pub representation_type: String,
-    pub variants: IndexMap<String, EnumVariantInfo>
+    pub variants: IndexMap<String, EnumVariantInfo>,
+    pub aliases: Vec<EnumAliasInfo>
 }
 
 #[derive(Debug)]
@@ -25,6 +26,13 @@ pub struct EnumVariantInfo {
     pub discriminant: Expr
 }
 
+/// Constant sharing its value with a variant, generated as an associated constant
+#[derive(Debug)]
+pub struct EnumAliasInfo {
+    pub ident: Ident,
+    pub target: Ident
+}
+
 impl From<&EnumVariantInfo> for Variant {
     fn from(value: &EnumVariantInfo) -> Self {
         Variant {
@@ -76,18 +84,21 @@ impl EnumInfo {
                 let attributes = variant.attributes.to_token_stream();
                 quote! {
                     #attributes
-                    #discriminant => Self::#ident,
+                    #discriminant => Ok(Self::#ident),
                 }
             })
             .collect::<TokenStream>();
+        let item_ident_string = item.ident.to_string();
         let from_representation_type_quote = quote! {
             #attributes_quote
-            impl From<#representation_type_quote> for #item_ident_quote {
-                fn from(value: #representation_type_quote) -> Self {
+            impl TryFrom<#representation_type_quote> for #item_ident_quote {
+                type Error = crate::utils::DeserializationError;
+
+                fn try_from(value: #representation_type_quote) -> Result<Self, Self::Error> {
                     let value: #spa_type_quote = value;
                     match value {
                         #from_representation_to_variant_quote
-                        _ => panic!("Unknown variant")
+                        _ => Err(crate::utils::DeserializationError::invalid_type(#item_ident_string, value))
                     }
                 }
             }
@@ -96,7 +107,7 @@ impl EnumInfo {
             #attributes_quote
             impl From<&#item_ident_quote> for #representation_type_quote {
                 fn from(value: &#item_ident_quote) -> Self {
-                    let value: #spa_type_quote = value.into();
+                    let value: #spa_type_quote = *value as #spa_type_quote;
                     value
                 }
             }
@@ -104,38 +115,77 @@ impl EnumInfo {
             #attributes_quote
             impl From<#item_ident_quote> for #representation_type_quote {
                 fn from(value: #item_ident_quote) -> Self {
-                    let value: #spa_type_quote = value.into();
+                    let value: #spa_type_quote = value as #spa_type_quote;
                     value
                 }
             }
         };
-        let from_variant_to_string_quote = self.variants.iter()
+        let from_variant_to_name_quote = self.variants.iter()
+            .map(|(_, variant)| {
+                let ident = variant.ident.to_token_stream();
+                let ident_string = variant.ident.to_string();
+                let attributes = variant.attributes.to_token_stream();
+                quote! {
+                    #attributes
+                    Self::#ident => #ident_string,
+                }
+            })
+            .collect::<TokenStream>();
+        let from_name_to_variant_quote = self.variants.iter()
             .map(|(_, variant)| {
                 let ident = variant.ident.to_token_stream();
                 let ident_string = variant.ident.to_string();
                 let attributes = variant.attributes.to_token_stream();
                 quote! {
                     #attributes
-                    Self::#ident => #ident_string.to_string(),
+                    #ident_string => Some(Self::#ident),
                 }
             })
             .collect::<TokenStream>();
-        let to_string_quote = quote! {
+        let name_quote = quote! {
             #attributes_quote
             impl #item_ident_quote {
-                fn to_string(&self) -> String {
+                /// Variant SPA short name (e.g. FL)
+                pub fn name(&self) -> &'static str {
                     match self {
-                        #from_variant_to_string_quote
+                        #from_variant_to_name_quote
+                    }
+                }
+
+                /// Variant matching its SPA short name (e.g. FL)
+                pub fn from_name(value: &str) -> Option<Self> {
+                    match value {
+                        #from_name_to_variant_quote
+                        _ => None
                     }
                 }
             }
         };
+        let aliases_quote = self.aliases.iter()
+            .map(|alias| {
+                let ident = alias.ident.to_token_stream();
+                let target = alias.target.to_token_stream();
+                quote! {
+                    pub const #ident: Self = Self::#target;
+                }
+            })
+            .collect::<TokenStream>();
+        let aliases_quote = match self.aliases.is_empty() {
+            true => quote! {},
+            false => quote! {
+                #attributes_quote
+                impl #item_ident_quote {
+                    #aliases_quote
+                }
+            },
+        };
         let items = vec![
             import_quote.to_string(),
             item_quote.to_string(),
             from_representation_type_quote.to_string(),
             to_representation_type_quote.to_string(),
-            to_string_quote.to_string(),
+            name_quote.to_string(),
+            aliases_quote.to_string(),
         ];
         let items = items.join("\n");
         let file = syn::parse_file(items.as_str()).unwrap();
@@ -0,0 +1,83 @@
+use crate::audio::{AudioChannel, AudioChannelPosition};
+use libspa::pod::deserialize::PodDeserializer;
+use libspa::pod::serialize::PodSerializer;
+use libspa::pod::{Value, ValueArray};
+use std::io::Cursor;
+use libspa::utils::Id;
+use std::str::FromStr;
+
+#[test]
+fn aux() {
+    assert_eq!(Some(AudioChannel::AUX0), AudioChannel::aux(0));
+    assert_eq!(Some(AudioChannel::AUX31), AudioChannel::aux(31));
+    assert_eq!(Some(AudioChannel::AUX63), AudioChannel::aux(63));
+    assert_eq!(None, AudioChannel::aux(64));
+    assert_eq!(Some(31), AudioChannel::AUX31.aux_index());
+    assert_eq!(None, AudioChannel::FL.aux_index());
+}
+
+#[test]
+fn name() {
+    assert_eq!("FL", AudioChannel::FL.name());
+    assert_eq!("AUX12", AudioChannel::AUX12.name());
+    assert_eq!(AudioChannel::FL.name(), AudioChannel::FL.to_string());
+    assert_eq!(Some(AudioChannel::LFE), AudioChannel::from_name(AudioChannel::LFE.name()));
+}
+
+#[test]
+fn format_position() {
+    let position = AudioChannelPosition::new(vec![
+        AudioChannel::FL, AudioChannel::FR, AudioChannel::FC, AudioChannel::LFE,
+    ]);
+    assert_eq!("[ FL FR FC LFE ]", position.to_string());
+    assert_eq!("[ ]", AudioChannelPosition::default().to_string());
+}
+
+#[test]
+fn parse_position() {
+    let expected = AudioChannelPosition::new(vec![
+        AudioChannel::FL, AudioChannel::FR, AudioChannel::AUX0, AudioChannel::AUX1,
+    ]);
+    assert_eq!(expected, AudioChannelPosition::from_str("[ FL FR AUX0 AUX1 ]").unwrap());
+    assert_eq!(expected, AudioChannelPosition::from_str("FL,FR,AUX0,AUX1").unwrap());
+    assert_eq!(AudioChannelPosition::default(), AudioChannelPosition::from_str("[ ]").unwrap());
+    assert!(AudioChannelPosition::from_str("[ FL XX ]").is_err());
+}
+
+#[test]
+fn parse_formatted_aux_position() {
+    let position = AudioChannelPosition::new(
+        (0..32).map(|index| AudioChannel::aux(index).unwrap()).collect()
+    );
+    assert_eq!(position, AudioChannelPosition::from_str(&position.to_string()).unwrap());
+}
+
+#[test]
+fn to_array() {
+    let position = AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FR]);
+    assert_eq!(
+        Some([AudioChannel::FL as u32, AudioChannel::FR as u32, AudioChannel::UNKNOWN as u32]),
+        position.to_array::<3>()
+    );
+    assert_eq!(None, position.to_array::<1>());
+}
+
+#[test]
+fn position_from_unknown_channel() {
+    // Custom channel range starts at 0x10000
+    let value = Value::ValueArray(ValueArray::Id(vec![Id(AudioChannel::FL.into()), Id(0x10000), Id(u32::MAX)]));
+    let position = AudioChannelPosition::try_from(value).unwrap();
+    assert_eq!(
+        AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::UNKNOWN, AudioChannel::UNKNOWN]),
+        position
+    );
+    // Position keeps its channels count once serialized back
+    let bytes = PodSerializer::serialize(Cursor::new(Vec::new()), &position).unwrap().0.into_inner();
+    let (_, deserialized) = PodDeserializer::deserialize_from::<AudioChannelPosition>(&bytes).unwrap();
+    assert_eq!(position, deserialized);
+    let value = Value::ValueArray(ValueArray::Id(vec![Id(AudioChannel::FL.into()), Id(AudioChannel::FR.into())]));
+    assert_eq!(
+        AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FR]),
+        AudioChannelPosition::try_from(value).unwrap()
+    );
+}
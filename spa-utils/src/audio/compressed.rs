@@ -0,0 +1,178 @@
+use crate::format::{format_properties, serialize_format, MediaType};
+use crate::impl_any_deserializer;
+use crate::utils::choice::SpaChoice;
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, ObjectPodSerializer, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
+use std::io::{Seek, Write};
+#[cfg(feature = "v0_3_65")]
+use crate::audio::AudioAacStreamFormat;
+#[cfg(feature = "v0_3_65")]
+use crate::param::{to_id, to_int};
+
+/// Rate and channels are optional for encoded streams, decoder can find them in the bitstream
+fn serialize_rate_and_channels<O: Write + Seek>(
+    object_serializer: &mut ObjectPodSerializer<O>,
+    rate: &Option<SpaChoice<u32>>,
+    channels: &Option<SpaChoice<u32>>,
+) -> Result<(), GenError> {
+    if let Some(rate) = rate {
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_AUDIO_rate,
+            rate,
+            PropertyFlags::empty(),
+        )?;
+    }
+    if let Some(channels) = channels {
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_AUDIO_channels,
+            channels,
+            PropertyFlags::empty(),
+        )?;
+    }
+    Ok(())
+}
+
+macro_rules! impl_compressed_info {
+    (
+        $(#[$attribute:meta])*
+        $name:ident,
+        $media_subtype:expr
+    ) => {
+        $(#[$attribute])*
+        #[derive(Debug, Clone, Default, PartialEq)]
+        pub struct $name {
+            pub rate: Option<SpaChoice<u32>>,
+            pub channels: Option<SpaChoice<u32>>,
+        }
+
+        impl TryFrom<Value> for $name {
+            type Error = DeserializationError;
+
+            fn try_from(value: Value) -> Result<Self, Self::Error> {
+                let mut properties = format_properties(value, MediaType::Audio.into(), $media_subtype)?;
+                Ok($name {
+                    rate: properties.optional(libspa::sys::SPA_FORMAT_AUDIO_rate, SpaChoice::try_from)?,
+                    channels: properties.optional(libspa::sys::SPA_FORMAT_AUDIO_channels, SpaChoice::try_from)?,
+                })
+            }
+        }
+
+        impl_any_deserializer!($name);
+
+        impl PodSerialize for $name {
+            fn serialize<O: Write + Seek>(
+                &self,
+                serializer: PodSerializer<O>
+            ) -> Result<SerializeSuccess<O>, GenError> {
+                let mut object_serializer = serialize_format(serializer, MediaType::Audio.into(), $media_subtype)?;
+                serialize_rate_and_channels(&mut object_serializer, &self.rate, &self.channels)?;
+                object_serializer.end()
+            }
+        }
+    }
+}
+
+impl_compressed_info!(
+    /// MPEG-1/2 audio layer III format
+    AudioInfoMp3,
+    libspa::sys::SPA_MEDIA_SUBTYPE_mp3
+);
+
+impl_compressed_info!(
+    /// Ogg Vorbis format
+    AudioInfoVorbis,
+    libspa::sys::SPA_MEDIA_SUBTYPE_vorbis
+);
+
+impl_compressed_info!(
+    /// RealAudio format
+    AudioInfoRa,
+    libspa::sys::SPA_MEDIA_SUBTYPE_ra
+);
+
+#[cfg(feature = "v0_3_65")]
+impl_compressed_info!(
+    /// Apple lossless format
+    AudioInfoAlac,
+    libspa::sys::SPA_MEDIA_SUBTYPE_alac
+);
+
+#[cfg(feature = "v0_3_65")]
+impl_compressed_info!(
+    /// Free lossless format
+    AudioInfoFlac,
+    libspa::sys::SPA_MEDIA_SUBTYPE_flac
+);
+
+#[cfg(feature = "v0_3_65")]
+impl_compressed_info!(
+    /// Monkey's audio format
+    AudioInfoApe,
+    libspa::sys::SPA_MEDIA_SUBTYPE_ape
+);
+
+#[cfg(feature = "v0_3_75")]
+impl_compressed_info!(
+    /// Opus format, subtype was added with PipeWire 0.3.68
+    AudioInfoOpus,
+    libspa::sys::SPA_MEDIA_SUBTYPE_opus
+);
+
+/// AAC format
+#[cfg(feature = "v0_3_65")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AudioInfoAac {
+    pub rate: Option<SpaChoice<u32>>,
+    pub channels: Option<SpaChoice<u32>>,
+    /// Bits per second
+    pub bitrate: Option<u32>,
+    pub stream_format: Option<AudioAacStreamFormat>,
+}
+
+#[cfg(feature = "v0_3_65")]
+impl TryFrom<Value> for AudioInfoAac {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = format_properties(value, MediaType::Audio.into(), libspa::sys::SPA_MEDIA_SUBTYPE_aac)?;
+        let bitrate = properties.optional(libspa::sys::SPA_FORMAT_AUDIO_bitrate, to_int)?;
+        let stream_format = properties.optional(libspa::sys::SPA_FORMAT_AUDIO_AAC_streamFormat, to_id)?;
+        Ok(AudioInfoAac {
+            rate: properties.optional(libspa::sys::SPA_FORMAT_AUDIO_rate, SpaChoice::try_from)?,
+            channels: properties.optional(libspa::sys::SPA_FORMAT_AUDIO_channels, SpaChoice::try_from)?,
+            bitrate: bitrate.map(|bitrate| bitrate as u32),
+            stream_format: stream_format.map(AudioAacStreamFormat::try_from).transpose()?,
+        })
+    }
+}
+
+#[cfg(feature = "v0_3_65")]
+impl_any_deserializer!(AudioInfoAac);
+
+#[cfg(feature = "v0_3_65")]
+impl PodSerialize for AudioInfoAac {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serialize_format(serializer, MediaType::Audio.into(), libspa::sys::SPA_MEDIA_SUBTYPE_aac)?;
+        serialize_rate_and_channels(&mut object_serializer, &self.rate, &self.channels)?;
+        if let Some(bitrate) = self.bitrate {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_FORMAT_AUDIO_bitrate,
+                &(bitrate as i32),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(stream_format) = &self.stream_format {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_FORMAT_AUDIO_AAC_streamFormat,
+                stream_format,
+                PropertyFlags::empty(),
+            )?;
+        }
+        object_serializer.end()
+    }
+}
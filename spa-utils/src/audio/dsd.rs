@@ -0,0 +1,81 @@
+use crate::audio::{AudioBitorder, AudioChannelPosition};
+use crate::format::{format_properties, serialize_format, MediaSubtype, MediaType};
+use crate::impl_any_deserializer;
+use crate::param::{to_id, to_int};
+use crate::utils::choice::SpaChoice;
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
+use std::io::{Seek, Write};
+
+/// DSD format, rate is the number of bytes per second and per channel
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioInfoDsd {
+    pub bitorder: Option<AudioBitorder>,
+    /// Bytes per channel before switching to next channel, negative values reverse bytes order
+    pub interleave: Option<i32>,
+    pub rate: SpaChoice<u32>,
+    pub channels: SpaChoice<u32>,
+    pub position: AudioChannelPosition,
+}
+
+impl TryFrom<Value> for AudioInfoDsd {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = format_properties(value, MediaType::Audio.into(), MediaSubtype::Dsd.into())?;
+        let bitorder = properties.optional(libspa::sys::SPA_FORMAT_AUDIO_bitorder, to_id)?;
+        let position = properties.optional(libspa::sys::SPA_FORMAT_AUDIO_position, AudioChannelPosition::try_from)?;
+        Ok(AudioInfoDsd {
+            bitorder: bitorder.map(AudioBitorder::try_from).transpose()?,
+            interleave: properties.optional(libspa::sys::SPA_FORMAT_AUDIO_interleave, to_int)?,
+            rate: properties.required(libspa::sys::SPA_FORMAT_AUDIO_rate, SpaChoice::try_from)?,
+            channels: properties.required(libspa::sys::SPA_FORMAT_AUDIO_channels, SpaChoice::try_from)?,
+            position: position.unwrap_or_default(),
+        })
+    }
+}
+
+impl_any_deserializer!(AudioInfoDsd);
+
+impl PodSerialize for AudioInfoDsd {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serialize_format(serializer, MediaType::Audio.into(), MediaSubtype::Dsd.into())?;
+        if let Some(bitorder) = &self.bitorder {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_FORMAT_AUDIO_bitorder,
+                bitorder,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(interleave) = self.interleave {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_FORMAT_AUDIO_interleave,
+                &interleave,
+                PropertyFlags::empty(),
+            )?;
+        }
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_AUDIO_rate,
+            &self.rate,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_AUDIO_channels,
+            &self.channels,
+            PropertyFlags::empty(),
+        )?;
+        if !self.position.is_empty() {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_FORMAT_AUDIO_position,
+                &self.position,
+                PropertyFlags::empty(),
+            )?;
+        }
+        object_serializer.end()
+    }
+}
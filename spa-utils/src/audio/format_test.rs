@@ -0,0 +1,86 @@
+use crate::audio::compressed::AudioInfoMp3;
+use crate::audio::dsd::AudioInfoDsd;
+use crate::audio::iec958::AudioInfoIec958;
+use crate::audio::{AudioBitorder, AudioChannel, AudioChannelPosition, AudioIec958Codec};
+use crate::utils::choice::SpaChoice;
+use libspa::pod::deserialize::{PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{PodSerialize, PodSerializer};
+use std::io::Cursor;
+
+fn serialize<P: PodSerialize>(value: &P) -> Vec<u8> {
+    PodSerializer::serialize(Cursor::new(Vec::new()), value)
+        .unwrap()
+        .0
+        .into_inner()
+}
+
+fn round_trip<P>(value: &P) -> P
+where
+    P: PodSerialize + for<'de> PodDeserialize<'de>
+{
+    let bytes = serialize(value);
+    let (_, value) = PodDeserializer::deserialize_from::<P>(&bytes).unwrap();
+    value
+}
+
+#[test]
+fn iec958() {
+    let format = AudioInfoIec958 {
+        codec: SpaChoice::Enum {
+            default: AudioIec958Codec::AC3,
+            alternatives: vec![AudioIec958Codec::AC3, AudioIec958Codec::DTS, AudioIec958Codec::EAC3],
+        },
+        rate: Some(SpaChoice::None(48000)),
+    };
+    assert_eq!(format, round_trip(&format));
+    let format = AudioInfoIec958 {
+        codec: SpaChoice::None(AudioIec958Codec::PCM),
+        rate: None,
+    };
+    assert_eq!(format, round_trip(&format));
+}
+
+#[test]
+fn dsd() {
+    let format = AudioInfoDsd {
+        bitorder: Some(AudioBitorder::msb),
+        interleave: Some(4),
+        rate: SpaChoice::None(352800),
+        channels: SpaChoice::None(2),
+        position: AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FR]),
+    };
+    assert_eq!(format, round_trip(&format));
+}
+
+#[test]
+fn mp3() {
+    let format = AudioInfoMp3 {
+        rate: Some(SpaChoice::Range {
+            default: 44100,
+            minimum: 8000,
+            maximum: 48000,
+        }),
+        channels: None,
+    };
+    assert_eq!(format, round_trip(&format));
+}
+
+#[cfg(feature = "v0_3_65")]
+#[test]
+fn aac() {
+    use crate::audio::compressed::AudioInfoAac;
+    use crate::audio::AudioAacStreamFormat;
+    let format = AudioInfoAac {
+        rate: Some(SpaChoice::None(44100)),
+        channels: Some(SpaChoice::None(2)),
+        bitrate: Some(128000),
+        stream_format: Some(AudioAacStreamFormat::MP4ADTS),
+    };
+    assert_eq!(format, round_trip(&format));
+}
+
+#[test]
+fn subtype_mismatch() {
+    let bytes = serialize(&AudioInfoMp3::default());
+    assert!(PodDeserializer::deserialize_from::<AudioInfoIec958>(&bytes).is_err());
+}
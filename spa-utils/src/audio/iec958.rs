@@ -0,0 +1,52 @@
+use crate::audio::AudioIec958Codec;
+use crate::format::{format_properties, serialize_format, MediaSubtype, MediaType};
+use crate::impl_any_deserializer;
+use crate::utils::choice::SpaChoice;
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
+use std::io::{Seek, Write};
+
+/// IEC958 (i.e. S/PDIF) format, encoded frames are passed through to the sink, e.g. HDMI receiver
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioInfoIec958 {
+    pub codec: SpaChoice<AudioIec958Codec>,
+    pub rate: Option<SpaChoice<u32>>,
+}
+
+impl TryFrom<Value> for AudioInfoIec958 {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = format_properties(value, MediaType::Audio.into(), MediaSubtype::Iec958.into())?;
+        Ok(AudioInfoIec958 {
+            codec: properties.required(libspa::sys::SPA_FORMAT_AUDIO_iec958Codec, SpaChoice::try_from)?,
+            rate: properties.optional(libspa::sys::SPA_FORMAT_AUDIO_rate, SpaChoice::try_from)?,
+        })
+    }
+}
+
+impl_any_deserializer!(AudioInfoIec958);
+
+impl PodSerialize for AudioInfoIec958 {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serialize_format(serializer, MediaType::Audio.into(), MediaSubtype::Iec958.into())?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_AUDIO_iec958Codec,
+            &self.codec,
+            PropertyFlags::empty(),
+        )?;
+        if let Some(rate) = &self.rate {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_FORMAT_AUDIO_rate,
+                rate,
+                PropertyFlags::empty(),
+            )?;
+        }
+        object_serializer.end()
+    }
+}
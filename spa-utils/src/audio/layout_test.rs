@@ -0,0 +1,72 @@
+use crate::audio::{AudioChannel, AudioChannelLayout, AudioChannelPosition};
+use std::str::FromStr;
+
+#[test]
+fn layout_to_position() {
+    let cases = vec![
+        (AudioChannelLayout::Mono, "[ MONO ]"),
+        (AudioChannelLayout::Stereo, "[ FL FR ]"),
+        (AudioChannelLayout::Surround2_1, "[ FL FR LFE ]"),
+        (AudioChannelLayout::Quad, "[ FL FR RL RR ]"),
+        (AudioChannelLayout::Surround5_0, "[ FL FR FC SL SR ]"),
+        (AudioChannelLayout::Surround5_1, "[ FL FR FC LFE SL SR ]"),
+        (AudioChannelLayout::Surround7_0, "[ FL FR FC SL SR RL RR ]"),
+        (AudioChannelLayout::Surround7_1, "[ FL FR FC LFE SL SR RL RR ]"),
+        (AudioChannelLayout::Surround7_1_4, "[ FL FR FC LFE SL SR RL RR TFL TFR TRL TRR ]"),
+        (AudioChannelLayout::Ambisonic(1), "[ AUX0 AUX1 AUX2 AUX3 ]"),
+    ];
+    for (layout, expected) in cases {
+        assert_eq!(expected, AudioChannelPosition::try_from(layout).unwrap().to_string());
+    }
+}
+
+#[test]
+fn detect_layout() {
+    for layout in [
+        AudioChannelLayout::Mono,
+        AudioChannelLayout::Stereo,
+        AudioChannelLayout::Surround2_1,
+        AudioChannelLayout::Quad,
+        AudioChannelLayout::Surround5_0,
+        AudioChannelLayout::Surround5_1,
+        AudioChannelLayout::Surround7_0,
+        AudioChannelLayout::Surround7_1,
+        AudioChannelLayout::Surround7_1_4,
+        AudioChannelLayout::Ambisonic(0),
+        AudioChannelLayout::Ambisonic(3),
+    ] {
+        assert_eq!(Some(layout), AudioChannelLayout::detect(&layout.try_into().unwrap()));
+    }
+}
+
+#[test]
+fn detect_layout_ignores_order() {
+    let position = AudioChannelPosition::from_str("[ FR FL ]").unwrap();
+    assert_eq!(Some(AudioChannelLayout::Stereo), AudioChannelLayout::detect(&position));
+}
+
+#[test]
+fn detect_unknown_layout() {
+    let position = AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FC]);
+    assert_eq!(None, AudioChannelLayout::detect(&position));
+    assert!(AudioChannelLayout::try_from(&position).is_err());
+    let position = AudioChannelPosition::from_str("[ AUX1 AUX0 AUX2 AUX3 ]").unwrap();
+    assert_eq!(None, AudioChannelLayout::detect(&position));
+}
+
+#[test]
+fn from_channels_count() {
+    assert_eq!(Some(AudioChannelLayout::Surround5_1), AudioChannelLayout::from_channels_count(6));
+    assert_eq!(Some(AudioChannelLayout::Surround7_0), AudioChannelLayout::from_channels_count(7));
+    assert_eq!(None, AudioChannelLayout::from_channels_count(9));
+}
+
+#[test]
+fn ambisonic_channels_limit() {
+    assert_eq!(64, AudioChannelLayout::Ambisonic(7).channels().unwrap().len());
+    assert!(AudioChannelLayout::Ambisonic(8).channels().is_err());
+    assert!(AudioChannelPosition::try_from(AudioChannelLayout::Ambisonic(8)).is_err());
+    // (order + 1)^2 overflows
+    assert!(AudioChannelLayout::Ambisonic(u32::MAX).channels().is_err());
+    assert!(AudioChannelLayout::Ambisonic(65535).channels().is_err());
+}
@@ -3,28 +3,91 @@ use libspa::pod::deserialize::DeserializeSuccess;
 use libspa::pod::deserialize::PodDeserialize;
 use libspa::pod::deserialize::PodDeserializer;
 use libspa::pod::deserialize::VecVisitor;
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{Value, ValueArray};
 use libspa::utils::Id;
-use std::convert::TryInto;
+use std::io::{Seek, Write};
+use std::fmt::{Display, Formatter};
 use std::ops::Deref;
-use crate::impl_array_id_deserializer;
-use crate::utils::IdOrEnumId;
+use std::str::FromStr;
+use crate::{impl_array_id_serializer, impl_choice_id_serializer, impl_id_serializer};
+use crate::utils::DeserializationError;
 
+pub mod compressed;
+pub mod dsd;
+pub mod iec958;
 pub mod raw;
 
+#[cfg(test)]
+#[path = "channel_test.rs"]
+mod channel_test;
+
+#[cfg(test)]
+#[path = "format_test.rs"]
+mod format_test;
+
+#[cfg(test)]
+#[path = "layout_test.rs"]
+mod layout_test;
+
+#[cfg(test)]
+#[path = "raw_test.rs"]
+mod raw_test;
+
+#[cfg(test)]
+#[path = "sample_format_test.rs"]
+mod sample_format_test;
+
 include!(concat!(env!("OUT_DIR"), "/audio.rs"));
 
-#[derive(Debug, Clone)]
-pub struct AudioSampleFormatEnum(IdOrEnumId<AudioSampleFormat>);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioSampleEndianness {
+    Little,
+    Big,
+}
 
-impl Deref for AudioSampleFormatEnum {
-    type Target = IdOrEnumId<AudioSampleFormat>;
+impl AudioSampleEndianness {
+    /// Planar formats are stored in host endianness
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Self = Self::Little;
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Self = Self::Big;
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+impl AudioChannel {
+    /// Auxiliary channel from its index, i.e. AUX0 to AUX63
+    pub fn aux(index: u32) -> Option<Self> {
+        if index > SPA_AUDIO_CHANNEL_AUX63 - SPA_AUDIO_CHANNEL_AUX0 {
+            return None;
+        }
+        Self::try_from(SPA_AUDIO_CHANNEL_AUX0 + index).ok()
+    }
+
+    pub fn aux_index(&self) -> Option<u32> {
+        let value: u32 = self.into();
+        match (SPA_AUDIO_CHANNEL_AUX0..=SPA_AUDIO_CHANNEL_AUX63).contains(&value) {
+            true => Some(value - SPA_AUDIO_CHANNEL_AUX0),
+            false => None,
+        }
+    }
+}
+
+impl Display for AudioChannel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for AudioChannel {
+    type Err = DeserializationError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        AudioChannel::from_name(value)
+            .ok_or(DeserializationError::invalid_type("AudioChannel", value))
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AudioChannelPosition(Vec<AudioChannel>);
 
 impl Default for AudioChannelPosition {
@@ -33,16 +96,186 @@ impl Default for AudioChannelPosition {
     }
 }
 
+impl From<Vec<AudioChannel>> for AudioChannelPosition {
+    fn from(value: Vec<AudioChannel>) -> Self {
+        AudioChannelPosition(value)
+    }
+}
+
 impl AudioChannelPosition {
-    pub fn to_array<const N: usize>(&self) -> [u32; N] {
-        let mut channels = self.0
-            .iter()
-            .map(move |channel| *channel as u32)
-            .collect::<Vec<u32>>();
-        if channels.len() < N {
-            channels.resize(N, AudioChannel::UNKNOWN as u32);
+    pub fn new(channels: Vec<AudioChannel>) -> Self {
+        AudioChannelPosition(channels)
+    }
+
+    /// Ids unknown to bindings (e.g. custom channels) are kept as UNKNOWN, a single one must not
+    /// discard the whole position
+    pub fn from_ids<I: IntoIterator<Item = u32>>(ids: I) -> Self {
+        AudioChannelPosition(
+            ids.into_iter()
+                .map(|id| AudioChannel::try_from(id).unwrap_or(AudioChannel::UNKNOWN))
+                .collect()
+        )
+    }
+
+    /// None when position does not fit, unused slots are set to UNKNOWN
+    /// since channels count tells how many of them are meaningful
+    pub fn to_array<const N: usize>(&self) -> Option<[u32; N]> {
+        if self.0.len() > N {
+            return None;
+        }
+        let mut channels = [AudioChannel::UNKNOWN as u32; N];
+        for (index, channel) in self.0.iter().enumerate() {
+            channels[index] = *channel as u32;
+        }
+        Some(channels)
+    }
+}
+
+impl TryFrom<Value> for AudioChannelPosition {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::ValueArray(ValueArray::Id(value)) => Ok(
+                AudioChannelPosition::from_ids(value.into_iter().map(|id| id.0))
+            ),
+            _ => Err(DeserializationError::invalid_type("Array<Id>", value)),
         }
-        channels.try_into().unwrap()
+    }
+}
+
+/// PipeWire textual syntax, e.g. [ FL FR FC LFE ]
+impl Display for AudioChannelPosition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let channels = self.0.iter()
+            .map(|channel| channel.to_string())
+            .collect::<Vec<_>>();
+        match channels.is_empty() {
+            true => write!(f, "[ ]"),
+            false => write!(f, "[ {} ]", channels.join(" ")),
+        }
+    }
+}
+
+/// Brackets are optional and channels can be separated by spaces or commas, e.g. FL,FR
+impl FromStr for AudioChannelPosition {
+    type Err = DeserializationError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let value = value.strip_prefix('[').unwrap_or(value);
+        let value = value.strip_suffix(']').unwrap_or(value);
+        let channels = value
+            .split(|character: char| character.is_whitespace() || character == ',')
+            .filter(|channel| channel.is_empty() == false)
+            .map(AudioChannel::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(AudioChannelPosition(channels))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioChannelLayout {
+    Mono,
+    Stereo,
+    Surround2_1,
+    Quad,
+    Surround5_0,
+    Surround5_1,
+    Surround7_0,
+    Surround7_1,
+    Surround7_1_4,
+    /// Ambisonic components are mapped to AUX channels in ACN order
+    Ambisonic(u32),
+}
+
+impl AudioChannelLayout {
+    /// Fails when channels do not fit in SPA_AUDIO_MAX_CHANNELS, i.e. ambisonic order above 7
+    pub fn channels(&self) -> Result<Vec<AudioChannel>, DeserializationError> {
+        use AudioChannel::*;
+        let too_many_channels = || DeserializationError::invalid_type(
+            &format!("at most {} channels", SPA_AUDIO_MAX_CHANNELS),
+            self
+        );
+        let channels = match self {
+            AudioChannelLayout::Mono => vec![MONO],
+            AudioChannelLayout::Stereo => vec![FL, FR],
+            AudioChannelLayout::Surround2_1 => vec![FL, FR, LFE],
+            AudioChannelLayout::Quad => vec![FL, FR, RL, RR],
+            AudioChannelLayout::Surround5_0 => vec![FL, FR, FC, SL, SR],
+            AudioChannelLayout::Surround5_1 => vec![FL, FR, FC, LFE, SL, SR],
+            AudioChannelLayout::Surround7_0 => vec![FL, FR, FC, SL, SR, RL, RR],
+            AudioChannelLayout::Surround7_1 => vec![FL, FR, FC, LFE, SL, SR, RL, RR],
+            AudioChannelLayout::Surround7_1_4 => vec![FL, FR, FC, LFE, SL, SR, RL, RR, TFL, TFR, TRL, TRR],
+            AudioChannelLayout::Ambisonic(order) => {
+                let count = order.checked_add(1)
+                    .and_then(|value| value.checked_mul(value))
+                    .filter(|count| *count <= SPA_AUDIO_MAX_CHANNELS)
+                    .ok_or_else(too_many_channels)?;
+                (0..count)
+                    .map(|index| AudioChannel::aux(index).ok_or_else(too_many_channels))
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+        };
+        Ok(channels)
+    }
+
+    /// Usual layout for a channels count, ambisonic layouts are never guessed
+    pub fn from_channels_count(channels: u32) -> Option<Self> {
+        match channels {
+            1 => Some(AudioChannelLayout::Mono),
+            2 => Some(AudioChannelLayout::Stereo),
+            3 => Some(AudioChannelLayout::Surround2_1),
+            4 => Some(AudioChannelLayout::Quad),
+            5 => Some(AudioChannelLayout::Surround5_0),
+            6 => Some(AudioChannelLayout::Surround5_1),
+            7 => Some(AudioChannelLayout::Surround7_0),
+            8 => Some(AudioChannelLayout::Surround7_1),
+            12 => Some(AudioChannelLayout::Surround7_1_4),
+            _ => None,
+        }
+    }
+
+    /// Channels order is not relevant for named layouts
+    pub fn detect(position: &AudioChannelPosition) -> Option<Self> {
+        let mut channels = position.0.clone();
+        channels.sort();
+        let layout = AudioChannelLayout::from_channels_count(channels.len() as u32)
+            .filter(|layout| {
+                // Named layouts always fit
+                let mut layout_channels = layout.channels().unwrap_or_default();
+                layout_channels.sort();
+                layout_channels == channels
+            });
+        if layout.is_some() {
+            return layout;
+        }
+        let order = (channels.len() as f64).sqrt() as u32;
+        if order == 0 {
+            return None;
+        }
+        let ambisonic = AudioChannelLayout::Ambisonic(order - 1);
+        match ambisonic.channels().is_ok_and(|channels| channels == position.0) {
+            true => Some(ambisonic),
+            false => None,
+        }
+    }
+}
+
+impl TryFrom<AudioChannelLayout> for AudioChannelPosition {
+    type Error = DeserializationError;
+
+    fn try_from(value: AudioChannelLayout) -> Result<Self, Self::Error> {
+        Ok(AudioChannelPosition(value.channels()?))
+    }
+}
+
+impl TryFrom<&AudioChannelPosition> for AudioChannelLayout {
+    type Error = DeserializationError;
+
+    fn try_from(value: &AudioChannelPosition) -> Result<Self, Self::Error> {
+        AudioChannelLayout::detect(value)
+            .ok_or(DeserializationError::invalid_type("AudioChannelLayout", value))
     }
 }
 
@@ -54,4 +287,27 @@ impl Deref for AudioChannelPosition {
     }
 }
 
-impl_array_id_deserializer!(AudioChannelPosition, AudioChannel);
\ No newline at end of file
+impl From<Vec<Id>> for AudioChannelPosition {
+    fn from(value: Vec<Id>) -> Self {
+        AudioChannelPosition::from_ids(value.into_iter().map(|id| id.0))
+    }
+}
+
+impl<'de> PodDeserialize<'de> for AudioChannelPosition {
+    fn deserialize(deserializer: PodDeserializer<'de>) -> Result<(Self, DeserializeSuccess<'de>), DeserializeError<&'de [u8]>>
+    where
+        Self: Sized
+    {
+        let res = deserializer.deserialize_array(VecVisitor::<Id>::default())?;
+        Ok((res.0.into(), res.1))
+    }
+}
+
+impl_array_id_serializer!(AudioChannelPosition);
+impl_id_serializer!(AudioSampleFormat);
+impl_id_serializer!(AudioChannel);
+impl_choice_id_serializer!(AudioSampleFormat);
+impl_id_serializer!(AudioIec958Codec);
+impl_id_serializer!(AudioBitorder);
+impl_id_serializer!(AudioAacStreamFormat);
+impl_choice_id_serializer!(AudioIec958Codec);
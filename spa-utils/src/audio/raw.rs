@@ -1,20 +1,43 @@
-use crate::format::MediaType;
+use crate::format::{format_properties, MediaType};
 use crate::format::MediaSubtype;
 use crate::audio::AudioSampleFormat;
 use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, ObjectPodDeserializer, PodDeserialize, PodDeserializer, Visitor};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
 use crate::audio::AudioChannelPosition;
-use crate::utils::{IdOrEnumId, IntOrChoiceInt, IntOrRangeInt32};
+use crate::utils::choice::SpaChoice;
+use crate::utils::DeserializationError;
+use std::io::{Seek, Write};
 
 #[derive(Debug, Clone)]
 pub struct AudioInfoRaw {
     pub media_type: MediaType,
     pub media_subtype: MediaSubtype,
-    pub sample_format: IdOrEnumId<AudioSampleFormat>,
-    pub sample_rate: IntOrRangeInt32,
-    pub channels: IntOrChoiceInt,
+    pub sample_format: SpaChoice<AudioSampleFormat>,
+    pub sample_rate: SpaChoice<u32>,
+    pub channels: SpaChoice<u32>,
     pub position: AudioChannelPosition
 }
 
+/// Unlike PodDeserialize, errors keep track of the offending property
+impl TryFrom<Value> for AudioInfoRaw {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = format_properties(value, MediaType::Audio.into(), MediaSubtype::Raw.into())?;
+        Ok(AudioInfoRaw {
+            media_type: MediaType::Audio,
+            media_subtype: MediaSubtype::Raw,
+            sample_format: properties.required(libspa::sys::SPA_FORMAT_AUDIO_format, SpaChoice::try_from)?,
+            sample_rate: properties.required(libspa::sys::SPA_FORMAT_AUDIO_rate, SpaChoice::try_from)?,
+            channels: properties.required(libspa::sys::SPA_FORMAT_AUDIO_channels, SpaChoice::try_from)?,
+            position: properties
+                .optional(libspa::sys::SPA_FORMAT_AUDIO_position, AudioChannelPosition::try_from)?
+                .unwrap_or_default(),
+        })
+    }
+}
+
 impl<'de> PodDeserialize<'de> for AudioInfoRaw {
     fn deserialize(
         deserializer: PodDeserializer<'de>,
@@ -62,4 +85,50 @@ impl<'de> PodDeserialize<'de> for AudioInfoRaw {
         }
         deserializer.deserialize_object(EnumFormatVisitor)
     }
-}
\ No newline at end of file
+}
+
+impl PodSerialize for AudioInfoRaw {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serializer.serialize_object(
+            libspa::sys::SPA_TYPE_OBJECT_Format,
+            libspa::sys::SPA_PARAM_EnumFormat,
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_mediaType,
+            &self.media_type,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_mediaSubtype,
+            &self.media_subtype,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_AUDIO_format,
+            &self.sample_format,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_AUDIO_rate,
+            &self.sample_rate,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_AUDIO_channels,
+            &self.channels,
+            PropertyFlags::empty(),
+        )?;
+        // Unpositioned audio (i.e. SPA_AUDIO_FLAG_UNPOSITIONED) has no position
+        if self.position.is_empty() == false {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_FORMAT_AUDIO_position,
+                &self.position,
+                PropertyFlags::empty(),
+            )?;
+        }
+        object_serializer.end()
+    }
+}
@@ -0,0 +1,226 @@
+use crate::audio::iec958::AudioInfoIec958;
+use crate::audio::raw::AudioInfoRaw;
+use crate::audio::{AudioChannel, AudioIec958Codec, AudioSampleFormat};
+use crate::format::{MediaSubtype, MediaType};
+use crate::utils::choice::SpaChoice;
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::PodDeserializer;
+use libspa::pod::serialize::PodSerializer;
+use std::io::Cursor;
+
+// Format object with fixed values:
+//   - format: Id(F32_LE)
+//   - rate: Int(48000)
+//   - channels: Int(2)
+//   - position: Array[FL, FR]
+const FIXED_FORMAT: [u8; 168] = [
+    0xa0, 0x00, 0x00, 0x00, 0x0f, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x04, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x1b, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+    0x80, 0xbb, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x05, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x10, 0x00, 0x00, 0x00, 0x0d, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+];
+
+// Format object with choices:
+//   - format: Choice(Enum, default: F32_LE, alternatives: [F32_LE, S16_LE])
+//   - rate: Choice(Range, default: 48000, min: 1, max: 384000)
+//   - channels: Int(2)
+//   - position: Array[FL, FR]
+const CHOICE_FORMAT: [u8; 216] = [
+    0xd0, 0x00, 0x00, 0x00, 0x0f, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x04, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x1c, 0x00, 0x00, 0x00, 0x13, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x1b, 0x01, 0x00, 0x00, 0x1b, 0x01, 0x00, 0x00,
+    0x03, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x1c, 0x00, 0x00, 0x00, 0x13, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+    0x80, 0xbb, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0xdc, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x05, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x10, 0x00, 0x00, 0x00, 0x0d, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+];
+
+// Format object with enumerated rates (e.g. USB interfaces):
+//   - format: Id(F32_LE)
+//   - rate: Choice(Enum, default: 48000, alternatives: [44100, 48000, 96000])
+//   - channels: Int(2)
+//   - position: Array[FL, FR]
+const ENUM_RATE_FORMAT: [u8; 192] = [
+    0xb8, 0x00, 0x00, 0x00, 0x0f, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x04, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x1b, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x20, 0x00, 0x00, 0x00, 0x13, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+    0x80, 0xbb, 0x00, 0x00, 0x44, 0xac, 0x00, 0x00,
+    0x80, 0xbb, 0x00, 0x00, 0x00, 0x77, 0x01, 0x00,
+    0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x05, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x10, 0x00, 0x00, 0x00, 0x0d, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+];
+
+fn deserialize(bytes: &[u8]) -> AudioInfoRaw {
+    let (_, value) = PodDeserializer::deserialize_from::<AudioInfoRaw>(bytes).unwrap();
+    value
+}
+
+fn serialize(value: &AudioInfoRaw) -> Vec<u8> {
+    PodSerializer::serialize(Cursor::new(Vec::new()), value)
+        .unwrap()
+        .0
+        .into_inner()
+}
+
+#[test]
+fn deserialize_fixed_format() {
+    let format = deserialize(&FIXED_FORMAT);
+    assert_eq!(MediaType::Audio, format.media_type);
+    assert_eq!(MediaSubtype::Raw, format.media_subtype);
+    assert_eq!(SpaChoice::None(AudioSampleFormat::F32_LE), format.sample_format);
+    assert_eq!(SpaChoice::None(48000), format.sample_rate);
+    assert_eq!(SpaChoice::None(2), format.channels);
+    assert_eq!(vec![AudioChannel::FL, AudioChannel::FR], *format.position);
+}
+
+#[test]
+fn deserialize_choice_format() {
+    let format = deserialize(&CHOICE_FORMAT);
+    assert_eq!(
+        SpaChoice::Enum {
+            default: AudioSampleFormat::F32_LE,
+            alternatives: vec![AudioSampleFormat::F32_LE, AudioSampleFormat::S16_LE],
+        },
+        format.sample_format
+    );
+    assert_eq!(
+        SpaChoice::Range {
+            default: 48000,
+            minimum: 1,
+            maximum: 384000,
+        },
+        format.sample_rate
+    );
+}
+
+#[test]
+fn round_trip_fixed_format() {
+    let format = deserialize(&FIXED_FORMAT);
+    assert_eq!(FIXED_FORMAT.to_vec(), serialize(&format));
+}
+
+#[test]
+fn round_trip_choice_format() {
+    let format = deserialize(&CHOICE_FORMAT);
+    assert_eq!(CHOICE_FORMAT.to_vec(), serialize(&format));
+}
+
+#[test]
+fn deserialize_enum_rate_format() {
+    let format = deserialize(&ENUM_RATE_FORMAT);
+    assert_eq!(
+        SpaChoice::Enum {
+            default: 48000,
+            alternatives: vec![44100, 48000, 96000],
+        },
+        format.sample_rate
+    );
+    assert_eq!(48000, format.sample_rate.default());
+}
+
+#[test]
+fn intersect_enum_rate_with_range_rate() {
+    let device = deserialize(&ENUM_RATE_FORMAT);
+    let stream = deserialize(&CHOICE_FORMAT);
+    let rates = device.sample_rate.intersect(&stream.sample_rate).unwrap();
+    assert_eq!(
+        SpaChoice::Enum {
+            default: 48000,
+            alternatives: vec![44100, 48000, 96000],
+        },
+        rates
+    );
+    assert_eq!(48000, rates.fixate());
+}
+
+#[test]
+fn round_trip_enum_rate_format() {
+    let format = deserialize(&ENUM_RATE_FORMAT);
+    assert_eq!(ENUM_RATE_FORMAT.to_vec(), serialize(&format));
+}
+
+#[test]
+fn try_from_fixed_format() {
+    let (_, value) = PodDeserializer::deserialize_any_from(&FIXED_FORMAT).unwrap();
+    let format = AudioInfoRaw::try_from(value).unwrap();
+    assert_eq!(SpaChoice::None(AudioSampleFormat::F32_LE), format.sample_format);
+    assert_eq!(SpaChoice::None(48000), format.sample_rate);
+    assert_eq!(SpaChoice::None(2), format.channels);
+    assert_eq!(vec![AudioChannel::FL, AudioChannel::FR], *format.position);
+}
+
+#[test]
+fn try_from_other_subtype_keeps_error_details() {
+    let format = AudioInfoIec958 {
+        codec: SpaChoice::None(AudioIec958Codec::AC3),
+        rate: None,
+    };
+    let bytes = PodSerializer::serialize(Cursor::new(Vec::new()), &format)
+        .unwrap()
+        .0
+        .into_inner();
+    let (_, value) = PodDeserializer::deserialize_any_from(&bytes).unwrap();
+    let error = AudioInfoRaw::try_from(value).unwrap_err();
+    assert_eq!(
+        DeserializationError::InvalidType {
+            expected: format!("media_subtype({})", u32::from(MediaSubtype::Raw)),
+            actual: format!("{:?}", u32::from(MediaSubtype::Iec958)),
+        },
+        error
+    );
+    assert_eq!(true, error.to_string().contains("media_subtype"));
+}
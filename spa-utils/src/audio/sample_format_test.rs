@@ -0,0 +1,71 @@
+use crate::audio::{AudioSampleEndianness, AudioSampleFormat};
+use crate::utils::DeserializationError;
+
+#[test]
+fn from_advertised_formats() {
+    assert_eq!(AudioSampleFormat::ALAW, AudioSampleFormat::try_from(libspa::sys::SPA_AUDIO_FORMAT_ALAW).unwrap());
+    assert_eq!(AudioSampleFormat::ULAW, AudioSampleFormat::try_from(libspa::sys::SPA_AUDIO_FORMAT_ULAW).unwrap());
+    assert_eq!(AudioSampleFormat::S20_LE, AudioSampleFormat::try_from(libspa::sys::SPA_AUDIO_FORMAT_S20_LE).unwrap());
+    assert_eq!(AudioSampleFormat::U18_BE, AudioSampleFormat::try_from(libspa::sys::SPA_AUDIO_FORMAT_U18_BE).unwrap());
+    assert_eq!(AudioSampleFormat::ENCODED, AudioSampleFormat::try_from(libspa::sys::SPA_AUDIO_FORMAT_ENCODED).unwrap());
+    assert_eq!(AudioSampleFormat::S24_32P, AudioSampleFormat::try_from(libspa::sys::SPA_AUDIO_FORMAT_DSP_S32).unwrap());
+}
+
+#[test]
+fn from_unknown_format() {
+    assert_eq!(
+        DeserializationError::InvalidType {
+            expected: "AudioSampleFormat".to_string(),
+            actual: u32::MAX.to_string(),
+        },
+        AudioSampleFormat::try_from(u32::MAX).unwrap_err()
+    );
+}
+
+#[test]
+fn aliases() {
+    assert_eq!(AudioSampleFormat::S24_32P, AudioSampleFormat::DSP_S32);
+    assert_eq!(AudioSampleFormat::F32P, AudioSampleFormat::DSP_F32);
+    assert_eq!(AudioSampleFormat::F64P, AudioSampleFormat::DSP_F64);
+}
+
+#[test]
+fn bytes_per_sample() {
+    assert_eq!(1, AudioSampleFormat::U8.bytes_per_sample());
+    assert_eq!(1, AudioSampleFormat::ALAW.bytes_per_sample());
+    assert_eq!(2, AudioSampleFormat::S16_LE.bytes_per_sample());
+    assert_eq!(3, AudioSampleFormat::S20_BE.bytes_per_sample());
+    assert_eq!(3, AudioSampleFormat::S24_LE.bytes_per_sample());
+    assert_eq!(4, AudioSampleFormat::S24_32P.bytes_per_sample());
+    assert_eq!(4, AudioSampleFormat::F32_LE.bytes_per_sample());
+    assert_eq!(8, AudioSampleFormat::F64P.bytes_per_sample());
+    assert_eq!(0, AudioSampleFormat::ENCODED.bytes_per_sample());
+}
+
+#[test]
+fn is_planar_and_is_float() {
+    assert!(AudioSampleFormat::F32P.is_planar());
+    assert!(AudioSampleFormat::F32P.is_float());
+    assert!(!AudioSampleFormat::F32_LE.is_planar());
+    assert!(AudioSampleFormat::F64_BE.is_float());
+    assert!(!AudioSampleFormat::S32_LE.is_float());
+    assert!(!AudioSampleFormat::ULAW.is_planar());
+}
+
+#[test]
+fn endianness() {
+    assert_eq!(Some(AudioSampleEndianness::Little), AudioSampleFormat::S16_LE.endianness());
+    assert_eq!(Some(AudioSampleEndianness::Big), AudioSampleFormat::F32_BE.endianness());
+    assert_eq!(Some(AudioSampleEndianness::NATIVE), AudioSampleFormat::S16P.endianness());
+    assert_eq!(None, AudioSampleFormat::U8.endianness());
+    assert_eq!(None, AudioSampleFormat::U8P.endianness());
+    assert_eq!(None, AudioSampleFormat::ALAW.endianness());
+}
+
+#[test]
+fn spa_name() {
+    assert_eq!("S16LE", AudioSampleFormat::S16_LE.spa_name());
+    assert_eq!("S24_32BE", AudioSampleFormat::S24_32_BE.spa_name());
+    assert_eq!("F32P", AudioSampleFormat::F32P.spa_name());
+    assert_eq!("U8", AudioSampleFormat::U8.spa_name());
+}
@@ -0,0 +1,110 @@
+use crate::control::{ControlEvent, ControlInfo, MidiEvent, SpaControl, SpaSequence};
+use crate::param::props::Props;
+use libspa::pod::deserialize::PodDeserializer;
+use libspa::pod::serialize::PodSerializer;
+use std::io::Cursor;
+
+fn sequence() -> SpaSequence {
+    SpaSequence {
+        unit: 0,
+        controls: vec![
+            SpaControl {
+                offset: 0,
+                event: ControlEvent::Midi(vec![0x90, 0x3c, 0x7f]),
+            },
+            SpaControl {
+                offset: 12,
+                event: ControlEvent::Properties(Props {
+                    volume: Some(0.5),
+                    ..Props::default()
+                }),
+            },
+            SpaControl {
+                offset: 64,
+                event: ControlEvent::Osc(b"/cue/1\0\0,\0\0\0".to_vec()),
+            },
+            SpaControl {
+                offset: 128,
+                event: ControlEvent::Ump(vec![0x2090_3c7f]),
+            },
+            SpaControl {
+                offset: 256,
+                event: ControlEvent::Midi(vec![0x80, 0x3c, 0x00]),
+            },
+        ],
+    }
+}
+
+#[test]
+fn round_trip() {
+    let sequence = sequence();
+    let bytes = sequence.to_bytes().unwrap();
+    assert_eq!(0, bytes.len() % 8);
+    assert_eq!(sequence, SpaSequence::from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn midi_events() {
+    assert_eq!(
+        vec![
+            MidiEvent {
+                offset: 0,
+                data: vec![0x90, 0x3c, 0x7f],
+            },
+            MidiEvent {
+                offset: 256,
+                data: vec![0x80, 0x3c, 0x00],
+            },
+        ],
+        sequence().midi_events()
+    );
+}
+
+#[test]
+fn from_midi_events() {
+    let events = vec![
+        MidiEvent {
+            offset: 256,
+            data: vec![0x80, 0x3c, 0x00],
+        },
+        MidiEvent {
+            offset: 0,
+            data: vec![0x90, 0x3c, 0x7f],
+        },
+    ];
+    let sequence = SpaSequence::from_midi_events(events);
+    assert_eq!(vec![0, 256], sequence.controls.iter().map(|control| control.offset).collect::<Vec<_>>());
+    let bytes = sequence.to_bytes().unwrap();
+    assert_eq!(
+        sequence().midi_events(),
+        SpaSequence::from_bytes(&bytes).unwrap().midi_events()
+    );
+}
+
+#[test]
+fn empty() {
+    let bytes = SpaSequence::default().to_bytes().unwrap();
+    assert_eq!(16, bytes.len());
+    assert_eq!(SpaSequence::default(), SpaSequence::from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn invalid() {
+    let mut bytes = sequence().to_bytes().unwrap();
+    assert!(SpaSequence::from_bytes(&bytes[..4]).is_err());
+    let length = bytes.len();
+    bytes.truncate(length - 8);
+    assert!(SpaSequence::from_bytes(&bytes).is_err());
+    bytes[4..8].copy_from_slice(&libspa::sys::SPA_TYPE_Struct.to_ne_bytes());
+    assert!(SpaSequence::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn control_info() {
+    let bytes = PodSerializer::serialize(Cursor::new(Vec::new()), &ControlInfo)
+        .unwrap()
+        .0
+        .into_inner();
+    let (_, value) = PodDeserializer::deserialize_from::<ControlInfo>(&bytes).unwrap();
+    assert_eq!(ControlInfo, value);
+}
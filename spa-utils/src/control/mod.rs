@@ -0,0 +1,210 @@
+use crate::format::{format_properties, serialize_format, MediaSubtype, MediaType};
+use crate::impl_any_deserializer;
+use crate::param::props::Props;
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::Value;
+use std::io::{Cursor, Seek, Write};
+
+#[cfg(test)]
+#[path = "control_test.rs"]
+mod control_test;
+
+/// UMP control type was added with PipeWire 1.2 and is not in every libspa bindings
+const SPA_CONTROL_UMP: u32 = 4;
+const POD_HEADER_SIZE: usize = 8;
+const CONTROL_HEADER_SIZE: usize = 8;
+
+/// Control stream format (i.e. application/control), buffers hold a sequence pod
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ControlInfo;
+
+impl TryFrom<Value> for ControlInfo {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        format_properties(value, MediaType::Application.into(), MediaSubtype::Control.into())?;
+        Ok(ControlInfo)
+    }
+}
+
+impl_any_deserializer!(ControlInfo);
+
+impl PodSerialize for ControlInfo {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let object_serializer = serialize_format(serializer, MediaType::Application.into(), MediaSubtype::Control.into())?;
+        object_serializer.end()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlEvent {
+    Properties(Props),
+    /// MIDI 1.0 message with its status byte
+    Midi(Vec<u8>),
+    /// OSC packet
+    Osc(Vec<u8>),
+    /// MIDI 2.0 universal packet as 32 bits words
+    Ump(Vec<u32>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpaControl {
+    /// Offset in samples from the start of the buffer
+    pub offset: u32,
+    pub event: ControlEvent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MidiEvent {
+    pub offset: u32,
+    pub data: Vec<u8>,
+}
+
+/// Sequence pod (i.e. spa_pod_sequence), controls are ordered by offset.
+/// libspa does not (de)serialize sequences so they are read from raw buffer data
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpaSequence {
+    pub unit: u32,
+    pub controls: Vec<SpaControl>,
+}
+
+fn read_u32(bytes: &[u8], position: usize) -> Result<u32, DeserializationError> {
+    match bytes.get(position..position + 4) {
+        Some(value) => Ok(u32::from_ne_bytes([value[0], value[1], value[2], value[3]])),
+        None => Err(DeserializationError::invalid_type("u32", format!("end of data at {}", position))),
+    }
+}
+
+fn pad(bytes: &mut Vec<u8>) {
+    let padding = (8 - bytes.len() % 8) % 8;
+    bytes.resize(bytes.len() + padding, 0);
+}
+
+fn to_bytes_pod(value: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(POD_HEADER_SIZE + value.len());
+    bytes.extend((value.len() as u32).to_ne_bytes());
+    bytes.extend(libspa::sys::SPA_TYPE_Bytes.to_ne_bytes());
+    bytes.extend(value);
+    bytes
+}
+
+impl SpaSequence {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializationError> {
+        let size = read_u32(bytes, 0)? as usize;
+        let type_ = read_u32(bytes, 4)?;
+        if type_ != libspa::sys::SPA_TYPE_Sequence {
+            return Err(DeserializationError::invalid_type("Sequence", type_));
+        }
+        let end = POD_HEADER_SIZE + size;
+        if bytes.len() < end {
+            return Err(DeserializationError::invalid_type(&format!("{} bytes", end), bytes.len()));
+        }
+        let unit = read_u32(bytes, POD_HEADER_SIZE)?;
+        let mut controls = vec![];
+        let mut position = POD_HEADER_SIZE + 8;
+        while position + CONTROL_HEADER_SIZE + POD_HEADER_SIZE <= end {
+            let offset = read_u32(bytes, position)?;
+            let control_type = read_u32(bytes, position + 4)?;
+            let value_position = position + CONTROL_HEADER_SIZE;
+            let value_size = read_u32(bytes, value_position)? as usize;
+            let value_type = read_u32(bytes, value_position + 4)?;
+            let body_position = value_position + POD_HEADER_SIZE;
+            let body = match bytes.get(body_position..body_position + value_size) {
+                Some(value) => value,
+                None => return Err(DeserializationError::invalid_type(&format!("{} bytes", value_size), "end of data")),
+            };
+            position = body_position + value_size + (8 - value_size % 8) % 8;
+            if control_type != libspa::sys::SPA_CONTROL_Properties && value_type != libspa::sys::SPA_TYPE_Bytes {
+                return Err(DeserializationError::invalid_type("Bytes", value_type));
+            }
+            let event = match control_type {
+                libspa::sys::SPA_CONTROL_Properties => {
+                    let value = &bytes[value_position..body_position + value_size];
+                    let value = match PodDeserializer::deserialize_any_from(value) {
+                        Ok((_, value)) => value,
+                        Err(_) => return Err(DeserializationError::invalid_type("Object", value_type)),
+                    };
+                    ControlEvent::Properties(Props::try_from(value)?)
+                }
+                libspa::sys::SPA_CONTROL_Midi => ControlEvent::Midi(body.to_vec()),
+                libspa::sys::SPA_CONTROL_OSC => ControlEvent::Osc(body.to_vec()),
+                SPA_CONTROL_UMP => ControlEvent::Ump(
+                    body.chunks_exact(4)
+                        .map(|word| u32::from_ne_bytes([word[0], word[1], word[2], word[3]]))
+                        .collect()
+                ),
+                // Unknown controls are skipped, as PipeWire does
+                _ => continue,
+            };
+            controls.push(SpaControl {
+                offset,
+                event,
+            });
+        }
+        Ok(SpaSequence {
+            unit,
+            controls,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, GenError> {
+        let mut body = vec![];
+        body.extend(self.unit.to_ne_bytes());
+        body.extend(0u32.to_ne_bytes());
+        for control in self.controls.iter() {
+            let (control_type, value) = match &control.event {
+                ControlEvent::Properties(value) => (
+                    libspa::sys::SPA_CONTROL_Properties,
+                    PodSerializer::serialize(Cursor::new(Vec::new()), value)?.0.into_inner()
+                ),
+                ControlEvent::Midi(value) => (libspa::sys::SPA_CONTROL_Midi, to_bytes_pod(value)),
+                ControlEvent::Osc(value) => (libspa::sys::SPA_CONTROL_OSC, to_bytes_pod(value)),
+                ControlEvent::Ump(value) => (
+                    SPA_CONTROL_UMP,
+                    to_bytes_pod(&value.iter().flat_map(|word| word.to_ne_bytes()).collect::<Vec<_>>())
+                ),
+            };
+            body.extend(control.offset.to_ne_bytes());
+            body.extend(control_type.to_ne_bytes());
+            body.extend(value);
+            pad(&mut body);
+        }
+        let mut bytes = Vec::with_capacity(POD_HEADER_SIZE + body.len());
+        bytes.extend((body.len() as u32).to_ne_bytes());
+        bytes.extend(libspa::sys::SPA_TYPE_Sequence.to_ne_bytes());
+        bytes.extend(body);
+        Ok(bytes)
+    }
+
+    /// Sequence holding MIDI events only, events are sorted by offset
+    pub fn from_midi_events(events: Vec<MidiEvent>) -> Self {
+        let mut controls = events.into_iter()
+            .map(|event| SpaControl {
+                offset: event.offset,
+                event: ControlEvent::Midi(event.data),
+            })
+            .collect::<Vec<_>>();
+        controls.sort_by_key(|control| control.offset);
+        SpaSequence {
+            unit: 0,
+            controls,
+        }
+    }
+
+    pub fn midi_events(&self) -> Vec<MidiEvent> {
+        self.controls.iter()
+            .filter_map(|control| match &control.event {
+                ControlEvent::Midi(data) => Some(MidiEvent {
+                    offset: control.offset,
+                    data: data.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
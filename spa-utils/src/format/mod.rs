@@ -3,10 +3,54 @@ use libspa::pod::deserialize::DeserializeSuccess;
 use libspa::pod::deserialize::IdVisitor;
 use libspa::pod::deserialize::PodDeserialize;
 use libspa::pod::deserialize::PodDeserializer;
+use libspa::pod::serialize::{GenError, ObjectPodSerializer, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
 use libspa::utils::Id;
-use crate::impl_id_deserializer;
+use std::io::{Seek, Write};
+use crate::{impl_id_deserializer, impl_id_serializer};
+use crate::param::{to_id, ObjectProperties};
+use crate::utils::DeserializationError;
 
 include!(concat!(env!("OUT_DIR"), "/format.rs"));
 
 impl_id_deserializer!(MediaType);
-impl_id_deserializer!(MediaSubtype);
\ No newline at end of file
+impl_id_deserializer!(MediaSubtype);
+impl_id_serializer!(MediaType);
+impl_id_serializer!(MediaSubtype);
+
+/// Format object properties, media type and subtype are checked.
+/// Ids are raw since some subtypes (e.g. Opus) are not exposed by libspa
+pub(crate) fn format_properties(value: Value, media_type: u32, media_subtype: u32) -> Result<ObjectProperties, DeserializationError> {
+    let mut properties = ObjectProperties::new(value, libspa::sys::SPA_TYPE_OBJECT_Format)?;
+    let actual_media_type = properties.required(libspa::sys::SPA_FORMAT_mediaType, to_id)?;
+    if actual_media_type != media_type {
+        return Err(DeserializationError::invalid_type(&format!("media_type({})", media_type), actual_media_type));
+    }
+    let actual_media_subtype = properties.required(libspa::sys::SPA_FORMAT_mediaSubtype, to_id)?;
+    if actual_media_subtype != media_subtype {
+        return Err(DeserializationError::invalid_type(&format!("media_subtype({})", media_subtype), actual_media_subtype));
+    }
+    Ok(properties)
+}
+
+pub(crate) fn serialize_format<O: Write + Seek>(
+    serializer: PodSerializer<O>,
+    media_type: u32,
+    media_subtype: u32
+) -> Result<ObjectPodSerializer<O>, GenError> {
+    let mut object_serializer = serializer.serialize_object(
+        libspa::sys::SPA_TYPE_OBJECT_Format,
+        libspa::sys::SPA_PARAM_EnumFormat,
+    )?;
+    object_serializer.serialize_property(
+        libspa::sys::SPA_FORMAT_mediaType,
+        &Id(media_type),
+        PropertyFlags::empty(),
+    )?;
+    object_serializer.serialize_property(
+        libspa::sys::SPA_FORMAT_mediaSubtype,
+        &Id(media_subtype),
+        PropertyFlags::empty(),
+    )?;
+    Ok(object_serializer)
+}
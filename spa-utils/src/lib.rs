@@ -2,4 +2,7 @@ extern crate libspa;
 mod macros;
 pub mod format;
 pub mod audio;
-pub mod utils;
\ No newline at end of file
+pub mod control;
+pub mod param;
+pub mod utils;
+pub mod video;
\ No newline at end of file
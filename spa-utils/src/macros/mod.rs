@@ -3,9 +3,11 @@ macro_rules! impl_id_deserializer {
     (
         $name:ident
     ) => {        
-        impl From<Id> for $name {
-            fn from(value: Id) -> Self {
-                value.0.into()
+        impl TryFrom<Id> for $name {
+            type Error = $crate::utils::DeserializationError;
+
+            fn try_from(value: Id) -> Result<Self, Self::Error> {
+                value.0.try_into()
             }
         }
 
@@ -15,7 +17,7 @@ macro_rules! impl_id_deserializer {
                 Self: Sized
             {
                 let res = deserializer.deserialize_id(IdVisitor)?;
-                Ok((res.0.into(), res.1))
+                Ok((res.0.try_into()?, res.1))
             }
         }
     }
@@ -49,9 +51,11 @@ macro_rules! impl_choice_int_deserializer {
     (
         $name:ident
     ) => {
-        impl From<Choice<i32>> for $name {
-            fn from(value: Choice<i32>) -> Self {
-                value.1.into()
+        impl TryFrom<Choice<i32>> for $name {
+            type Error = DeserializationError;
+
+            fn try_from(value: Choice<i32>) -> Result<Self, Self::Error> {
+                value.1.try_into()
             }
         }
 
@@ -61,7 +65,7 @@ macro_rules! impl_choice_int_deserializer {
                 Self: Sized
             {
                 let res = deserializer.deserialize_choice(ChoiceIntVisitor)?;
-                Ok((res.0.into(), res.1))
+                Ok((res.0.try_into()?, res.1))
             }
         }
     }
@@ -73,15 +77,22 @@ macro_rules! impl_array_id_deserializer {
         $array_name:ident,
         $item_name:ident
     ) => {
-        impl From<&Id> for $item_name {
-            fn from(value: &Id) -> Self {
-                value.0.into()
+        impl TryFrom<&Id> for $item_name {
+            type Error = $crate::utils::DeserializationError;
+
+            fn try_from(value: &Id) -> Result<Self, Self::Error> {
+                value.0.try_into()
             }
         }
 
-        impl From<Vec<Id>> for $array_name {
-            fn from(value: Vec<Id>) -> Self {
-                $array_name(value.iter().map(|id| id.into()).collect())
+        impl TryFrom<Vec<Id>> for $array_name {
+            type Error = $crate::utils::DeserializationError;
+
+            fn try_from(value: Vec<Id>) -> Result<Self, Self::Error> {
+                let items = value.iter()
+                    .map(|id| id.try_into())
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok($array_name(items))
             }
         }
 
@@ -90,8 +101,8 @@ macro_rules! impl_array_id_deserializer {
             where
                 Self: Sized
             {
-                let res = deserializer.deserialize_array(VecVisitor::default())?;
-                Ok((res.0.into(), res.1))
+                let res = deserializer.deserialize_array(VecVisitor::<Id>::default())?;
+                Ok((res.0.try_into()?, res.1))
             }
         }
     }
@@ -108,8 +119,56 @@ macro_rules! impl_any_deserializer {
                 Self: Sized
             {
                 let res = deserializer.deserialize_any()?;
-                Ok((res.0.into(), res.1))
+                Ok((res.0.try_into()?, res.1))
+            }
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! impl_id_serializer {
+    (
+        $name:ident
+    ) => {
+        impl PodSerialize for $name {
+            fn serialize<O: Write + Seek>(&self, serializer: PodSerializer<O>) -> Result<SerializeSuccess<O>, GenError> {
+                serializer.serialized_fixed_sized_pod(&Id((*self).into()))
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[macro_export]
+macro_rules! impl_array_id_serializer {
+    (
+        $array_name:ident
+    ) => {
+        impl PodSerialize for $array_name {
+            fn serialize<O: Write + Seek>(&self, serializer: PodSerializer<O>) -> Result<SerializeSuccess<O>, GenError> {
+                let mut array_serializer = serializer.serialize_array::<Id>(self.0.len() as u32)?;
+                for item in self.0.iter() {
+                    array_serializer.serialize_element(&Id((*item).into()))?;
+                }
+                array_serializer.end()
+            }
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! impl_choice_id_serializer {
+    (
+        $name:ident
+    ) => {
+        impl PodSerialize for $crate::utils::choice::SpaChoice<$name> {
+            fn serialize<O: Write + Seek>(&self, serializer: PodSerializer<O>) -> Result<SerializeSuccess<O>, GenError> {
+                match self {
+                    $crate::utils::choice::SpaChoice::None(value) => {
+                        serializer.serialized_fixed_sized_pod(&Id((*value).into()))
+                    }
+                    _ => serializer.serialize_choice(&self.to_choice(|value| Id((*value).into()))),
+                }
+            }
+        }
+    }
+}
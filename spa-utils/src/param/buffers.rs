@@ -0,0 +1,70 @@
+use crate::impl_any_deserializer;
+use crate::param::ObjectProperties;
+use crate::utils::choice::SpaChoice;
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
+use std::io::{Seek, Write};
+
+/// Buffers param (i.e. SPA_PARAM_Buffers), values are usually ranges during negotiation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Buffers {
+    pub buffers: Option<SpaChoice<u32>>,
+    pub blocks: Option<SpaChoice<u32>>,
+    pub size: Option<SpaChoice<u32>>,
+    pub stride: Option<SpaChoice<u32>>,
+    pub align: Option<SpaChoice<u32>>,
+    /// Data types flags, i.e. 1 << SPA_DATA_MemPtr
+    pub data_type: Option<SpaChoice<u32>>,
+}
+
+impl Buffers {
+    fn fields(&self) -> [(u32, &Option<SpaChoice<u32>>); 6] {
+        [
+            (libspa::sys::SPA_PARAM_BUFFERS_buffers, &self.buffers),
+            (libspa::sys::SPA_PARAM_BUFFERS_blocks, &self.blocks),
+            (libspa::sys::SPA_PARAM_BUFFERS_size, &self.size),
+            (libspa::sys::SPA_PARAM_BUFFERS_stride, &self.stride),
+            (libspa::sys::SPA_PARAM_BUFFERS_align, &self.align),
+            (libspa::sys::SPA_PARAM_BUFFERS_dataType, &self.data_type),
+        ]
+    }
+}
+
+impl TryFrom<Value> for Buffers {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = ObjectProperties::new(value, libspa::sys::SPA_TYPE_OBJECT_ParamBuffers)?;
+        let mut take = |key: u32| properties.optional(key, SpaChoice::<u32>::try_from);
+        Ok(Buffers {
+            buffers: take(libspa::sys::SPA_PARAM_BUFFERS_buffers)?,
+            blocks: take(libspa::sys::SPA_PARAM_BUFFERS_blocks)?,
+            size: take(libspa::sys::SPA_PARAM_BUFFERS_size)?,
+            stride: take(libspa::sys::SPA_PARAM_BUFFERS_stride)?,
+            align: take(libspa::sys::SPA_PARAM_BUFFERS_align)?,
+            data_type: take(libspa::sys::SPA_PARAM_BUFFERS_dataType)?,
+        })
+    }
+}
+
+impl_any_deserializer!(Buffers);
+
+impl PodSerialize for Buffers {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serializer.serialize_object(
+            libspa::sys::SPA_TYPE_OBJECT_ParamBuffers,
+            libspa::sys::SPA_PARAM_Buffers,
+        )?;
+        for (key, value) in self.fields() {
+            if let Some(value) = value {
+                object_serializer.serialize_property(key, value, PropertyFlags::empty())?;
+            }
+        }
+        object_serializer.end()
+    }
+}
@@ -0,0 +1,66 @@
+use crate::impl_any_deserializer;
+use crate::param::{to_id, to_int, ObjectProperties};
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
+use libspa::utils::Id;
+use std::io::{Seek, Write};
+
+/// IO param (i.e. SPA_PARAM_IO), area a node can share with its peer.
+/// Id is raw since io types are extended across versions (e.g. SPA_IO_AsyncBuffers)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Io {
+    /// Area type, i.e. SPA_IO_Buffers
+    pub id: u32,
+    /// Area size in bytes
+    pub size: u32,
+}
+
+impl Io {
+    pub fn new(id: u32, size: u32) -> Self {
+        Self {
+            id,
+            size,
+        }
+    }
+}
+
+impl TryFrom<Value> for Io {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = ObjectProperties::new(value, libspa::sys::SPA_TYPE_OBJECT_ParamIO)?;
+        let size = properties.required(libspa::sys::SPA_PARAM_IO_size, to_int)?;
+        Ok(Io {
+            id: properties.required(libspa::sys::SPA_PARAM_IO_id, to_id)?,
+            size: u32::try_from(size)
+                .map_err(|_| DeserializationError::invalid_type("positive size", size))?,
+        })
+    }
+}
+
+impl_any_deserializer!(Io);
+
+impl PodSerialize for Io {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serializer.serialize_object(
+            libspa::sys::SPA_TYPE_OBJECT_ParamIO,
+            libspa::sys::SPA_PARAM_IO,
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_IO_id,
+            &Id(self.id),
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_IO_size,
+            &(self.size as i32),
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.end()
+    }
+}
@@ -0,0 +1,153 @@
+use crate::impl_any_deserializer;
+use crate::param::{to_float, to_id, to_int, to_long, ObjectProperties, ParamDirection};
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
+use libspa::utils::Id;
+use std::io::{Seek, Write};
+
+/// Latency param (i.e. SPA_PARAM_Latency), missing bounds are zero
+#[derive(Debug, Clone, PartialEq)]
+pub struct Latency {
+    pub direction: ParamDirection,
+    pub min_quantum: f32,
+    pub max_quantum: f32,
+    pub min_rate: i32,
+    pub max_rate: i32,
+    pub min_ns: i64,
+    pub max_ns: i64,
+}
+
+impl Latency {
+    pub fn new(direction: ParamDirection) -> Self {
+        Self {
+            direction,
+            min_quantum: 0.0,
+            max_quantum: 0.0,
+            min_rate: 0,
+            max_rate: 0,
+            min_ns: 0,
+            max_ns: 0,
+        }
+    }
+}
+
+impl TryFrom<Value> for Latency {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = ObjectProperties::new(value, libspa::sys::SPA_TYPE_OBJECT_ParamLatency)?;
+        let direction = properties.required(libspa::sys::SPA_PARAM_LATENCY_direction, to_id)?;
+        Ok(Latency {
+            direction: direction.try_into()?,
+            min_quantum: properties.optional(libspa::sys::SPA_PARAM_LATENCY_minQuantum, to_float)?.unwrap_or_default(),
+            max_quantum: properties.optional(libspa::sys::SPA_PARAM_LATENCY_maxQuantum, to_float)?.unwrap_or_default(),
+            min_rate: properties.optional(libspa::sys::SPA_PARAM_LATENCY_minRate, to_int)?.unwrap_or_default(),
+            max_rate: properties.optional(libspa::sys::SPA_PARAM_LATENCY_maxRate, to_int)?.unwrap_or_default(),
+            min_ns: properties.optional(libspa::sys::SPA_PARAM_LATENCY_minNs, to_long)?.unwrap_or_default(),
+            max_ns: properties.optional(libspa::sys::SPA_PARAM_LATENCY_maxNs, to_long)?.unwrap_or_default(),
+        })
+    }
+}
+
+impl_any_deserializer!(Latency);
+
+impl PodSerialize for Latency {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serializer.serialize_object(
+            libspa::sys::SPA_TYPE_OBJECT_ParamLatency,
+            libspa::sys::SPA_PARAM_Latency,
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_LATENCY_direction,
+            &Id(self.direction.into()),
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_LATENCY_minQuantum,
+            &self.min_quantum,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_LATENCY_maxQuantum,
+            &self.max_quantum,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_LATENCY_minRate,
+            &self.min_rate,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_LATENCY_maxRate,
+            &self.max_rate,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_LATENCY_minNs,
+            &self.min_ns,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_LATENCY_maxNs,
+            &self.max_ns,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.end()
+    }
+}
+
+/// Process latency param (i.e. SPA_PARAM_ProcessLatency), latency added by node itself
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessLatency {
+    pub quantum: f32,
+    pub rate: i32,
+    pub ns: i64,
+}
+
+impl TryFrom<Value> for ProcessLatency {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = ObjectProperties::new(value, libspa::sys::SPA_TYPE_OBJECT_ParamProcessLatency)?;
+        Ok(ProcessLatency {
+            quantum: properties.optional(libspa::sys::SPA_PARAM_PROCESS_LATENCY_quantum, to_float)?.unwrap_or_default(),
+            rate: properties.optional(libspa::sys::SPA_PARAM_PROCESS_LATENCY_rate, to_int)?.unwrap_or_default(),
+            ns: properties.optional(libspa::sys::SPA_PARAM_PROCESS_LATENCY_ns, to_long)?.unwrap_or_default(),
+        })
+    }
+}
+
+impl_any_deserializer!(ProcessLatency);
+
+impl PodSerialize for ProcessLatency {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serializer.serialize_object(
+            libspa::sys::SPA_TYPE_OBJECT_ParamProcessLatency,
+            libspa::sys::SPA_PARAM_ProcessLatency,
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_PROCESS_LATENCY_quantum,
+            &self.quantum,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_PROCESS_LATENCY_rate,
+            &self.rate,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_PROCESS_LATENCY_ns,
+            &self.ns,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.end()
+    }
+}
@@ -0,0 +1,226 @@
+use libspa::pod::deserialize::DeserializeError;
+use libspa::pod::deserialize::DeserializeSuccess;
+use libspa::pod::deserialize::IdVisitor;
+use libspa::pod::deserialize::PodDeserialize;
+use libspa::pod::deserialize::PodDeserializer;
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{Object, Value, ValueArray};
+use libspa::utils::Id;
+use std::io::{Cursor, Seek, Write};
+use crate::{impl_id_deserializer, impl_id_serializer};
+use crate::utils::DeserializationError;
+
+pub mod buffers;
+pub mod io;
+pub mod latency;
+pub mod port_config;
+pub mod profile;
+pub mod props;
+pub mod route;
+
+#[cfg(test)]
+#[path = "param_test.rs"]
+mod param_test;
+
+include!(concat!(env!("OUT_DIR"), "/param.rs"));
+
+impl_id_deserializer!(ParamType);
+impl_id_serializer!(ParamType);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamDirection {
+    Input,
+    Output,
+}
+
+impl TryFrom<u32> for ParamDirection {
+    type Error = DeserializationError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            SPA_DIRECTION_INPUT => Ok(ParamDirection::Input),
+            SPA_DIRECTION_OUTPUT => Ok(ParamDirection::Output),
+            _ => Err(DeserializationError::invalid_type("spa_direction", value)),
+        }
+    }
+}
+
+impl From<ParamDirection> for u32 {
+    fn from(value: ParamDirection) -> Self {
+        match value {
+            ParamDirection::Input => SPA_DIRECTION_INPUT,
+            ParamDirection::Output => SPA_DIRECTION_OUTPUT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamAvailability {
+    Unknown,
+    No,
+    Yes,
+}
+
+impl TryFrom<u32> for ParamAvailability {
+    type Error = DeserializationError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            SPA_PARAM_AVAILABILITY_unknown => Ok(ParamAvailability::Unknown),
+            SPA_PARAM_AVAILABILITY_no => Ok(ParamAvailability::No),
+            SPA_PARAM_AVAILABILITY_yes => Ok(ParamAvailability::Yes),
+            _ => Err(DeserializationError::invalid_type("spa_param_availability", value)),
+        }
+    }
+}
+
+impl From<ParamAvailability> for u32 {
+    fn from(value: ParamAvailability) -> Self {
+        match value {
+            ParamAvailability::Unknown => SPA_PARAM_AVAILABILITY_unknown,
+            ParamAvailability::No => SPA_PARAM_AVAILABILITY_no,
+            ParamAvailability::Yes => SPA_PARAM_AVAILABILITY_yes,
+        }
+    }
+}
+
+/// Properties of a param object, keys are not ordered and most of them are optional
+pub(crate) struct ObjectProperties(Vec<(u32, Value)>);
+
+impl ObjectProperties {
+    pub(crate) fn new(value: Value, object_type: u32) -> Result<Self, DeserializationError> {
+        match value {
+            Value::Object(Object { type_, properties, .. }) if type_ == object_type => {
+                Ok(Self(
+                    properties.into_iter()
+                        .map(|property| (property.key, property.value))
+                        .collect()
+                ))
+            }
+            _ => Err(DeserializationError::invalid_type("Object", value)),
+        }
+    }
+
+    pub(crate) fn take(&mut self, key: u32) -> Option<Value> {
+        let index = self.0.iter().position(|(property_key, _)| *property_key == key)?;
+        Some(self.0.remove(index).1)
+    }
+
+    pub(crate) fn required<T, F>(&mut self, key: u32, map: F) -> Result<T, DeserializationError>
+    where
+        F: FnOnce(Value) -> Result<T, DeserializationError>
+    {
+        match self.take(key) {
+            Some(value) => map(value),
+            None => Err(DeserializationError::invalid_type(&format!("property({})", key), "None")),
+        }
+    }
+
+    pub(crate) fn optional<T, F>(&mut self, key: u32, map: F) -> Result<Option<T>, DeserializationError>
+    where
+        F: FnOnce(Value) -> Result<T, DeserializationError>
+    {
+        self.take(key).map(map).transpose()
+    }
+}
+
+pub(crate) fn to_int(value: Value) -> Result<i32, DeserializationError> {
+    match value {
+        Value::Int(value) => Ok(value),
+        _ => Err(DeserializationError::invalid_type("Int", value)),
+    }
+}
+
+pub(crate) fn to_long(value: Value) -> Result<i64, DeserializationError> {
+    match value {
+        Value::Long(value) => Ok(value),
+        _ => Err(DeserializationError::invalid_type("Long", value)),
+    }
+}
+
+pub(crate) fn to_float(value: Value) -> Result<f32, DeserializationError> {
+    match value {
+        Value::Float(value) => Ok(value),
+        _ => Err(DeserializationError::invalid_type("Float", value)),
+    }
+}
+
+pub(crate) fn to_bool(value: Value) -> Result<bool, DeserializationError> {
+    match value {
+        Value::Bool(value) => Ok(value),
+        _ => Err(DeserializationError::invalid_type("Bool", value)),
+    }
+}
+
+pub(crate) fn to_id(value: Value) -> Result<u32, DeserializationError> {
+    match value {
+        Value::Id(value) => Ok(value.0),
+        _ => Err(DeserializationError::invalid_type("Id", value)),
+    }
+}
+
+pub(crate) fn to_string(value: Value) -> Result<String, DeserializationError> {
+    match value {
+        Value::String(value) => Ok(value),
+        _ => Err(DeserializationError::invalid_type("String", value)),
+    }
+}
+
+pub(crate) fn to_int_array(value: Value) -> Result<Vec<i32>, DeserializationError> {
+    match value {
+        Value::ValueArray(ValueArray::Int(value)) => Ok(value),
+        _ => Err(DeserializationError::invalid_type("Array<Int>", value)),
+    }
+}
+
+pub(crate) fn to_float_array(value: Value) -> Result<Vec<f32>, DeserializationError> {
+    match value {
+        Value::ValueArray(ValueArray::Float(value)) => Ok(value),
+        _ => Err(DeserializationError::invalid_type("Array<Float>", value)),
+    }
+}
+
+/// Info dictionary is a struct with items count followed by key and value strings
+pub(crate) fn to_info(value: Value) -> Result<Vec<(String, String)>, DeserializationError> {
+    let values = match value {
+        Value::Struct(value) => value,
+        _ => return Err(DeserializationError::invalid_type("Struct", value)),
+    };
+    let mut values = values.into_iter();
+    let count = match values.next() {
+        Some(value) => to_int(value)?,
+        None => return Ok(vec![]),
+    };
+    let mut info = vec![];
+    for _ in 0..count {
+        match (values.next(), values.next()) {
+            (Some(key), Some(value)) => info.push((to_string(key)?, to_string(value)?)),
+            _ => return Err(DeserializationError::invalid_type("String", "None")),
+        }
+    }
+    Ok(info)
+}
+
+pub(crate) fn from_info(info: &Vec<(String, String)>) -> Value {
+    let mut values = vec![Value::Int(info.len() as i32)];
+    for (key, value) in info.iter() {
+        values.push(Value::String(key.clone()));
+        values.push(Value::String(value.clone()));
+    }
+    Value::Struct(values)
+}
+
+/// Nested objects (e.g. PortConfig format) are re-serialized to reuse their own deserializer
+pub(crate) fn to_pod<P>(value: Value) -> Result<P, DeserializationError>
+where
+    P: for<'de> PodDeserialize<'de>
+{
+    let bytes = match PodSerializer::serialize(Cursor::new(Vec::new()), &value) {
+        Ok(result) => result.0.into_inner(),
+        Err(_) => return Err(DeserializationError::invalid_type("Pod", value)),
+    };
+    match PodDeserializer::deserialize_from::<P>(&bytes) {
+        Ok((_, value)) => Ok(value),
+        Err(_) => Err(DeserializationError::invalid_type(std::any::type_name::<P>(), value)),
+    }
+}
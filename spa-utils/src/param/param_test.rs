@@ -0,0 +1,191 @@
+use crate::audio::raw::AudioInfoRaw;
+use crate::audio::{AudioChannel, AudioChannelPosition, AudioSampleFormat};
+use crate::format::{MediaSubtype, MediaType};
+use crate::param::buffers::Buffers;
+use crate::param::io::Io;
+use crate::param::latency::{Latency, ProcessLatency};
+use crate::param::port_config::{PortConfig, PortConfigMode};
+use crate::param::profile::Profile;
+use crate::param::props::Props;
+use crate::param::route::Route;
+use crate::param::{ParamAvailability, ParamDirection, ParamType};
+use crate::utils::choice::SpaChoice;
+use libspa::pod::deserialize::{PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{PodSerialize, PodSerializer};
+use libspa::pod::{Object, Property, Value, ValueArray};
+use libspa::utils::Id;
+use std::io::Cursor;
+
+fn round_trip<P>(value: &P) -> P
+where
+    P: PodSerialize + for<'de> PodDeserialize<'de>
+{
+    let bytes = PodSerializer::serialize(Cursor::new(Vec::new()), value)
+        .unwrap()
+        .0
+        .into_inner();
+    let (_, value) = PodDeserializer::deserialize_from::<P>(&bytes).unwrap();
+    value
+}
+
+#[test]
+fn param_type() {
+    assert_eq!(ParamType::Props, ParamType::try_from(libspa::sys::SPA_PARAM_Props).unwrap());
+    assert_eq!(ParamType::EnumFormat, ParamType::try_from(libspa::sys::SPA_PARAM_EnumFormat).unwrap());
+    assert_eq!(libspa::sys::SPA_PARAM_Latency, u32::from(ParamType::Latency));
+    assert_eq!(ParamType::IO, round_trip(&ParamType::IO));
+}
+
+#[test]
+fn props() {
+    let props = Props {
+        volume: Some(0.5),
+        mute: Some(false),
+        channel_volumes: Some(vec![0.25, 1.0]),
+        channel_map: Some(AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FR])),
+        soft_volumes: Some(vec![1.0, 1.0]),
+        params: Some(vec![
+            ("node.description".to_string(), Value::String("Speakers".to_string())),
+            ("monitor.channel-volumes".to_string(), Value::Bool(true)),
+        ]),
+    };
+    assert_eq!(props, round_trip(&props));
+}
+
+#[test]
+fn props_with_unknown_channel() {
+    let value = Value::Object(Object {
+        type_: libspa::sys::SPA_TYPE_OBJECT_Props,
+        id: libspa::sys::SPA_PARAM_Props,
+        properties: vec![
+            Property::new(
+                libspa::sys::SPA_PROP_channelMap,
+                Value::ValueArray(ValueArray::Id(vec![Id(AudioChannel::FL.into()), Id(u32::MAX)]))
+            ),
+        ],
+    });
+    let props = Props::try_from(value).unwrap();
+    assert_eq!(
+        Some(AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::UNKNOWN])),
+        props.channel_map
+    );
+    assert_eq!(props, round_trip(&props));
+}
+
+#[test]
+fn props_with_missing_values() {
+    let props = Props {
+        mute: Some(true),
+        ..Props::default()
+    };
+    assert_eq!(props, round_trip(&props));
+}
+
+#[test]
+fn latency() {
+    let latency = Latency {
+        min_quantum: 1.0,
+        max_quantum: 1.0,
+        min_rate: 256,
+        max_rate: 256,
+        min_ns: 1000,
+        max_ns: 2000,
+        ..Latency::new(ParamDirection::Output)
+    };
+    assert_eq!(latency, round_trip(&latency));
+    let process_latency = ProcessLatency {
+        quantum: 0.5,
+        rate: 128,
+        ns: 500,
+    };
+    assert_eq!(process_latency, round_trip(&process_latency));
+}
+
+#[test]
+fn buffers() {
+    let buffers = Buffers {
+        buffers: Some(SpaChoice::Range {
+            default: 8,
+            minimum: 2,
+            maximum: 16,
+        }),
+        blocks: Some(SpaChoice::None(1)),
+        size: Some(SpaChoice::None(4096)),
+        stride: Some(SpaChoice::None(4)),
+        ..Buffers::default()
+    };
+    assert_eq!(buffers, round_trip(&buffers));
+}
+
+#[test]
+fn io() {
+    let io = Io::new(libspa::sys::SPA_IO_Buffers, 8);
+    assert_eq!(io, round_trip(&io));
+    let bytes = PodSerializer::serialize(Cursor::new(Vec::new()), &io)
+        .unwrap()
+        .0
+        .into_inner();
+    let (_, value) = PodDeserializer::deserialize_any_from(&bytes).unwrap();
+    let io = Io::try_from(value).unwrap();
+    assert_eq!(libspa::sys::SPA_IO_Buffers, io.id);
+    assert_eq!(8, io.size);
+}
+
+#[test]
+fn route() {
+    let route = Route {
+        device: Some(1),
+        name: Some("analog-output-headphones".to_string()),
+        description: Some("Headphones".to_string()),
+        priority: Some(9900),
+        available: Some(ParamAvailability::Yes),
+        info: Some(vec![("port.type".to_string(), "headphones".to_string())]),
+        profiles: Some(vec![1, 2]),
+        props: Some(Props {
+            volume: Some(1.0),
+            ..Props::default()
+        }),
+        devices: Some(vec![1]),
+        profile: Some(1),
+        save: Some(true),
+        ..Route::new(0, ParamDirection::Output)
+    };
+    assert_eq!(route, round_trip(&route));
+}
+
+#[test]
+fn profile() {
+    let profile = Profile {
+        name: Some("output:analog-stereo".to_string()),
+        description: Some("Analog Stereo Output".to_string()),
+        priority: Some(6500),
+        available: Some(ParamAvailability::Unknown),
+        info: Some(vec![]),
+        save: Some(false),
+        ..Profile::new(1)
+    };
+    assert_eq!(profile, round_trip(&profile));
+}
+
+#[test]
+fn port_config() {
+    let mut port_config = PortConfig::new(ParamDirection::Input, PortConfigMode::Dsp);
+    port_config.monitor = Some(true);
+    port_config.format = Some(AudioInfoRaw {
+        media_type: MediaType::Audio,
+        media_subtype: MediaSubtype::Raw,
+        sample_format: SpaChoice::None(AudioSampleFormat::F32P),
+        sample_rate: SpaChoice::None(48000),
+        channels: SpaChoice::None(2),
+        position: AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FR]),
+    });
+    let value = round_trip(&port_config);
+    assert_eq!(ParamDirection::Input, value.direction);
+    assert_eq!(PortConfigMode::Dsp, value.mode);
+    assert_eq!(Some(true), value.monitor);
+    assert_eq!(None, value.control);
+    let format = value.format.unwrap();
+    assert_eq!(SpaChoice::None(AudioSampleFormat::F32P), format.sample_format);
+    assert_eq!(SpaChoice::None(2), format.channels);
+    assert_eq!(AudioChannelPosition::new(vec![AudioChannel::FL, AudioChannel::FR]), format.position);
+}
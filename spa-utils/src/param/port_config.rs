@@ -0,0 +1,128 @@
+use crate::audio::raw::AudioInfoRaw;
+use crate::impl_any_deserializer;
+use crate::param::{to_bool, to_id, to_pod, ObjectProperties, ParamDirection};
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
+use libspa::utils::Id;
+use std::io::{Seek, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortConfigMode {
+    None,
+    Passthrough,
+    Convert,
+    Dsp,
+}
+
+impl TryFrom<u32> for PortConfigMode {
+    type Error = DeserializationError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            libspa::sys::SPA_PARAM_PORT_CONFIG_MODE_none => Ok(PortConfigMode::None),
+            libspa::sys::SPA_PARAM_PORT_CONFIG_MODE_passthrough => Ok(PortConfigMode::Passthrough),
+            libspa::sys::SPA_PARAM_PORT_CONFIG_MODE_convert => Ok(PortConfigMode::Convert),
+            libspa::sys::SPA_PARAM_PORT_CONFIG_MODE_dsp => Ok(PortConfigMode::Dsp),
+            _ => Err(DeserializationError::invalid_type("spa_param_port_config_mode", value)),
+        }
+    }
+}
+
+impl From<PortConfigMode> for u32 {
+    fn from(value: PortConfigMode) -> Self {
+        match value {
+            PortConfigMode::None => libspa::sys::SPA_PARAM_PORT_CONFIG_MODE_none,
+            PortConfigMode::Passthrough => libspa::sys::SPA_PARAM_PORT_CONFIG_MODE_passthrough,
+            PortConfigMode::Convert => libspa::sys::SPA_PARAM_PORT_CONFIG_MODE_convert,
+            PortConfigMode::Dsp => libspa::sys::SPA_PARAM_PORT_CONFIG_MODE_dsp,
+        }
+    }
+}
+
+/// Port config param (i.e. SPA_PARAM_PortConfig), how adapter exposes node ports
+#[derive(Debug, Clone)]
+pub struct PortConfig {
+    pub direction: ParamDirection,
+    pub mode: PortConfigMode,
+    pub monitor: Option<bool>,
+    pub control: Option<bool>,
+    /// Ports format for DSP and convert modes
+    pub format: Option<AudioInfoRaw>,
+}
+
+impl PortConfig {
+    pub fn new(direction: ParamDirection, mode: PortConfigMode) -> Self {
+        Self {
+            direction,
+            mode,
+            monitor: None,
+            control: None,
+            format: None,
+        }
+    }
+}
+
+impl TryFrom<Value> for PortConfig {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = ObjectProperties::new(value, libspa::sys::SPA_TYPE_OBJECT_ParamPortConfig)?;
+        let direction = properties.required(libspa::sys::SPA_PARAM_PORT_CONFIG_direction, to_id)?;
+        let mode = properties.required(libspa::sys::SPA_PARAM_PORT_CONFIG_mode, to_id)?;
+        Ok(PortConfig {
+            direction: direction.try_into()?,
+            mode: mode.try_into()?,
+            monitor: properties.optional(libspa::sys::SPA_PARAM_PORT_CONFIG_monitor, to_bool)?,
+            control: properties.optional(libspa::sys::SPA_PARAM_PORT_CONFIG_control, to_bool)?,
+            format: properties.optional(libspa::sys::SPA_PARAM_PORT_CONFIG_format, to_pod)?,
+        })
+    }
+}
+
+impl_any_deserializer!(PortConfig);
+
+impl PodSerialize for PortConfig {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serializer.serialize_object(
+            libspa::sys::SPA_TYPE_OBJECT_ParamPortConfig,
+            libspa::sys::SPA_PARAM_PortConfig,
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_PORT_CONFIG_direction,
+            &Id(self.direction.into()),
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_PORT_CONFIG_mode,
+            &Id(self.mode.into()),
+            PropertyFlags::empty(),
+        )?;
+        if let Some(monitor) = self.monitor {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_PORT_CONFIG_monitor,
+                &monitor,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(control) = self.control {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_PORT_CONFIG_control,
+                &control,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(format) = &self.format {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_PORT_CONFIG_format,
+                format,
+                PropertyFlags::empty(),
+            )?;
+        }
+        object_serializer.end()
+    }
+}
@@ -0,0 +1,125 @@
+use crate::impl_any_deserializer;
+use crate::param::{from_info, to_bool, to_id, to_info, to_int, to_string, ObjectProperties, ParamAvailability};
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
+use libspa::utils::Id;
+use std::io::{Seek, Write};
+
+/// Profile param (i.e. SPA_PARAM_Profile or SPA_PARAM_EnumProfile), a device configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub index: i32,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub priority: Option<i32>,
+    pub available: Option<ParamAvailability>,
+    pub info: Option<Vec<(String, String)>>,
+    /// Struct of nodes classes with their count and routes, kept as is
+    pub classes: Option<Value>,
+    pub save: Option<bool>,
+}
+
+impl Profile {
+    pub fn new(index: i32) -> Self {
+        Self {
+            index,
+            name: None,
+            description: None,
+            priority: None,
+            available: None,
+            info: None,
+            classes: None,
+            save: None,
+        }
+    }
+}
+
+impl TryFrom<Value> for Profile {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = ObjectProperties::new(value, libspa::sys::SPA_TYPE_OBJECT_ParamProfile)?;
+        let available = properties.optional(libspa::sys::SPA_PARAM_PROFILE_available, to_id)?;
+        Ok(Profile {
+            index: properties.required(libspa::sys::SPA_PARAM_PROFILE_index, to_int)?,
+            name: properties.optional(libspa::sys::SPA_PARAM_PROFILE_name, to_string)?,
+            description: properties.optional(libspa::sys::SPA_PARAM_PROFILE_description, to_string)?,
+            priority: properties.optional(libspa::sys::SPA_PARAM_PROFILE_priority, to_int)?,
+            available: available.map(ParamAvailability::try_from).transpose()?,
+            info: properties.optional(libspa::sys::SPA_PARAM_PROFILE_info, to_info)?,
+            classes: properties.take(libspa::sys::SPA_PARAM_PROFILE_classes),
+            save: properties.optional(libspa::sys::SPA_PARAM_PROFILE_save, to_bool)?,
+        })
+    }
+}
+
+impl_any_deserializer!(Profile);
+
+impl PodSerialize for Profile {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serializer.serialize_object(
+            libspa::sys::SPA_TYPE_OBJECT_ParamProfile,
+            libspa::sys::SPA_PARAM_Profile,
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_PROFILE_index,
+            &self.index,
+            PropertyFlags::empty(),
+        )?;
+        if let Some(name) = &self.name {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_PROFILE_name,
+                name.as_str(),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(description) = &self.description {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_PROFILE_description,
+                description.as_str(),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(priority) = self.priority {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_PROFILE_priority,
+                &priority,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(available) = self.available {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_PROFILE_available,
+                &Id(available.into()),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(info) = &self.info {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_PROFILE_info,
+                &from_info(info),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(classes) = &self.classes {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_PROFILE_classes,
+                classes,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(save) = self.save {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_PROFILE_save,
+                &save,
+                PropertyFlags::empty(),
+            )?;
+        }
+        object_serializer.end()
+    }
+}
@@ -0,0 +1,116 @@
+use crate::audio::AudioChannelPosition;
+use crate::impl_any_deserializer;
+use crate::param::{to_bool, to_float, to_float_array, ObjectProperties};
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value, ValueArray};
+use std::io::{Seek, Write};
+
+/// Props param (i.e. SPA_PARAM_Props), unset values are left untouched by node
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Props {
+    pub volume: Option<f32>,
+    pub mute: Option<bool>,
+    pub channel_volumes: Option<Vec<f32>>,
+    pub channel_map: Option<AudioChannelPosition>,
+    pub soft_volumes: Option<Vec<f32>>,
+    /// Key and value pairs, e.g. node properties or plugin parameters
+    pub params: Option<Vec<(String, Value)>>,
+}
+
+fn to_params(value: Value) -> Result<Vec<(String, Value)>, DeserializationError> {
+    let values = match value {
+        Value::Struct(value) => value,
+        _ => return Err(DeserializationError::invalid_type("Struct", value)),
+    };
+    let mut values = values.into_iter();
+    let mut params = vec![];
+    while let Some(key) = values.next() {
+        let key = match key {
+            Value::String(key) => key,
+            _ => return Err(DeserializationError::invalid_type("String", key)),
+        };
+        match values.next() {
+            Some(value) => params.push((key, value)),
+            None => return Err(DeserializationError::invalid_type("Value", "None")),
+        }
+    }
+    Ok(params)
+}
+
+impl TryFrom<Value> for Props {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = ObjectProperties::new(value, libspa::sys::SPA_TYPE_OBJECT_Props)?;
+        Ok(Props {
+            volume: properties.optional(libspa::sys::SPA_PROP_volume, to_float)?,
+            mute: properties.optional(libspa::sys::SPA_PROP_mute, to_bool)?,
+            channel_volumes: properties.optional(libspa::sys::SPA_PROP_channelVolumes, to_float_array)?,
+            channel_map: properties.optional(libspa::sys::SPA_PROP_channelMap, AudioChannelPosition::try_from)?,
+            soft_volumes: properties.optional(libspa::sys::SPA_PROP_softVolumes, to_float_array)?,
+            params: properties.optional(libspa::sys::SPA_PROP_params, to_params)?,
+        })
+    }
+}
+
+impl_any_deserializer!(Props);
+
+impl PodSerialize for Props {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serializer.serialize_object(
+            libspa::sys::SPA_TYPE_OBJECT_Props,
+            libspa::sys::SPA_PARAM_Props,
+        )?;
+        if let Some(volume) = self.volume {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PROP_volume,
+                &volume,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(mute) = self.mute {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PROP_mute,
+                &mute,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(channel_volumes) = &self.channel_volumes {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PROP_channelVolumes,
+                &Value::ValueArray(ValueArray::Float(channel_volumes.clone())),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(channel_map) = &self.channel_map {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PROP_channelMap,
+                channel_map,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(soft_volumes) = &self.soft_volumes {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PROP_softVolumes,
+                &Value::ValueArray(ValueArray::Float(soft_volumes.clone())),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(params) = &self.params {
+            let params = params.iter()
+                .flat_map(|(key, value)| vec![Value::String(key.clone()), value.clone()])
+                .collect::<Vec<_>>();
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PROP_params,
+                &Value::Struct(params),
+                PropertyFlags::empty(),
+            )?;
+        }
+        object_serializer.end()
+    }
+}
@@ -0,0 +1,174 @@
+use crate::impl_any_deserializer;
+use crate::param::props::Props;
+use crate::param::{from_info, to_bool, to_id, to_info, to_int, to_int_array, to_string, ObjectProperties, ParamAvailability, ParamDirection};
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value, ValueArray};
+use libspa::utils::Id;
+use std::io::{Seek, Write};
+
+/// Route param (i.e. SPA_PARAM_Route or SPA_PARAM_EnumRoute), a device port like headphones
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    pub index: i32,
+    pub direction: ParamDirection,
+    pub device: Option<i32>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub priority: Option<i32>,
+    pub available: Option<ParamAvailability>,
+    pub info: Option<Vec<(String, String)>>,
+    pub profiles: Option<Vec<i32>>,
+    pub props: Option<Props>,
+    pub devices: Option<Vec<i32>>,
+    pub profile: Option<i32>,
+    pub save: Option<bool>,
+}
+
+impl Route {
+    pub fn new(index: i32, direction: ParamDirection) -> Self {
+        Self {
+            index,
+            direction,
+            device: None,
+            name: None,
+            description: None,
+            priority: None,
+            available: None,
+            info: None,
+            profiles: None,
+            props: None,
+            devices: None,
+            profile: None,
+            save: None,
+        }
+    }
+}
+
+impl TryFrom<Value> for Route {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = ObjectProperties::new(value, libspa::sys::SPA_TYPE_OBJECT_ParamRoute)?;
+        let direction = properties.required(libspa::sys::SPA_PARAM_ROUTE_direction, to_id)?;
+        let available = properties.optional(libspa::sys::SPA_PARAM_ROUTE_available, to_id)?;
+        Ok(Route {
+            index: properties.required(libspa::sys::SPA_PARAM_ROUTE_index, to_int)?,
+            direction: direction.try_into()?,
+            device: properties.optional(libspa::sys::SPA_PARAM_ROUTE_device, to_int)?,
+            name: properties.optional(libspa::sys::SPA_PARAM_ROUTE_name, to_string)?,
+            description: properties.optional(libspa::sys::SPA_PARAM_ROUTE_description, to_string)?,
+            priority: properties.optional(libspa::sys::SPA_PARAM_ROUTE_priority, to_int)?,
+            available: available.map(ParamAvailability::try_from).transpose()?,
+            info: properties.optional(libspa::sys::SPA_PARAM_ROUTE_info, to_info)?,
+            profiles: properties.optional(libspa::sys::SPA_PARAM_ROUTE_profiles, to_int_array)?,
+            props: properties.optional(libspa::sys::SPA_PARAM_ROUTE_props, Props::try_from)?,
+            devices: properties.optional(libspa::sys::SPA_PARAM_ROUTE_devices, to_int_array)?,
+            profile: properties.optional(libspa::sys::SPA_PARAM_ROUTE_profile, to_int)?,
+            save: properties.optional(libspa::sys::SPA_PARAM_ROUTE_save, to_bool)?,
+        })
+    }
+}
+
+impl_any_deserializer!(Route);
+
+impl PodSerialize for Route {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serializer.serialize_object(
+            libspa::sys::SPA_TYPE_OBJECT_ParamRoute,
+            libspa::sys::SPA_PARAM_Route,
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_ROUTE_index,
+            &self.index,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_PARAM_ROUTE_direction,
+            &Id(self.direction.into()),
+            PropertyFlags::empty(),
+        )?;
+        if let Some(device) = self.device {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_ROUTE_device,
+                &device,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(name) = &self.name {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_ROUTE_name,
+                name.as_str(),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(description) = &self.description {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_ROUTE_description,
+                description.as_str(),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(priority) = self.priority {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_ROUTE_priority,
+                &priority,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(available) = self.available {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_ROUTE_available,
+                &Id(available.into()),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(info) = &self.info {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_ROUTE_info,
+                &from_info(info),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(profiles) = &self.profiles {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_ROUTE_profiles,
+                &Value::ValueArray(ValueArray::Int(profiles.clone())),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(props) = &self.props {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_ROUTE_props,
+                props,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(devices) = &self.devices {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_ROUTE_devices,
+                &Value::ValueArray(ValueArray::Int(devices.clone())),
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(profile) = self.profile {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_ROUTE_profile,
+                &profile,
+                PropertyFlags::empty(),
+            )?;
+        }
+        if let Some(save) = self.save {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_PARAM_ROUTE_save,
+                &save,
+                PropertyFlags::empty(),
+            )?;
+        }
+        object_serializer.end()
+    }
+}
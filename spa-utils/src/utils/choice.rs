@@ -0,0 +1,360 @@
+use crate::utils::DeserializationError;
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{ChoiceValue, Value};
+use libspa::utils::{Choice, ChoiceEnum, ChoiceFlags};
+use std::io::{Seek, Write};
+
+/// Enum alternatives are kept in order they were advertised, first one being the preferred
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpaChoice<T> {
+    None(T),
+    Range {
+        default: T,
+        minimum: T,
+        maximum: T,
+    },
+    Step {
+        default: T,
+        minimum: T,
+        maximum: T,
+        step: T,
+    },
+    Enum {
+        default: T,
+        alternatives: Vec<T>,
+    },
+    Flags {
+        default: T,
+        flags: Vec<T>,
+    },
+}
+
+fn clamp<T: PartialOrd>(value: T, minimum: T, maximum: T) -> T {
+    if value < minimum {
+        minimum
+    } else if value > maximum {
+        maximum
+    } else {
+        value
+    }
+}
+
+fn as_u32<T: Into<u32>>(value: T) -> u32 {
+    value.into()
+}
+
+/// Type name without its module path, e.g. AudioChannel
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+fn greatest_common_divisor(a: u32, b: u32) -> u32 {
+    if b == 0 { a } else { greatest_common_divisor(b, a % b) }
+}
+
+impl <T: Copy + PartialOrd + Into<u32> + TryFrom<u32>> SpaChoice<T> {
+    pub fn default(&self) -> T {
+        match self {
+            SpaChoice::None(value) => *value,
+            SpaChoice::Range { default, .. }
+            | SpaChoice::Step { default, .. }
+            | SpaChoice::Enum { default, .. }
+            | SpaChoice::Flags { default, .. } => *default,
+        }
+    }
+
+    /// Enum default comes first unless alternatives already hold it, as in SPA choice layout
+    fn values(&self) -> Vec<T> {
+        match self {
+            SpaChoice::Enum { default, alternatives } if alternatives.contains(default) => alternatives.clone(),
+            SpaChoice::Enum { default, alternatives } => {
+                let mut values = vec![*default];
+                values.extend(alternatives.iter().cloned());
+                values
+            }
+            _ => vec![self.default()],
+        }
+    }
+
+    fn bounds(&self) -> (T, T, u32) {
+        match self {
+            SpaChoice::Range { minimum, maximum, .. } => (*minimum, *maximum, 1),
+            SpaChoice::Step { minimum, maximum, step, .. } => (*minimum, *maximum, as_u32(*step).max(1)),
+            _ => (self.default(), self.default(), 1),
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        match self {
+            SpaChoice::None(expected) => *expected == value,
+            SpaChoice::Range { minimum, maximum, .. } => *minimum <= value && value <= *maximum,
+            SpaChoice::Step { minimum, maximum, step, .. } => {
+                if value < *minimum || value > *maximum {
+                    return false;
+                }
+                let step = as_u32(*step);
+                step == 0 || (as_u32(value) - as_u32(*minimum)) % step == 0
+            }
+            SpaChoice::Enum { default, alternatives } => {
+                *default == value || alternatives.contains(&value)
+            }
+            SpaChoice::Flags { flags, .. } => {
+                let mask = flags.iter().fold(0u32, |mask, flag| mask | as_u32(*flag));
+                as_u32(value) & !mask == 0
+            }
+        }
+    }
+
+    fn intersect_values(&self, other: &Self, preferred: T) -> Option<Self> {
+        let alternatives = self.values()
+            .into_iter()
+            .filter(|value| other.contains(*value))
+            .collect::<Vec<_>>();
+        let default = match alternatives.contains(&preferred) {
+            true => preferred,
+            false => *alternatives.first()?,
+        };
+        Some(SpaChoice::Enum {
+            default,
+            alternatives,
+        })
+    }
+
+    /// Values allowed by both choices, self default value is kept when possible
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        match (self, other) {
+            (SpaChoice::None(value), _) | (_, SpaChoice::None(value)) => {
+                match self.contains(*value) && other.contains(*value) {
+                    true => Some(SpaChoice::None(*value)),
+                    false => None,
+                }
+            }
+            (SpaChoice::Enum { .. }, _) => self.intersect_values(other, self.default()),
+            (_, SpaChoice::Enum { .. }) => other.intersect_values(self, self.default()),
+            (
+                SpaChoice::Flags { default, flags },
+                SpaChoice::Flags { flags: other_flags, .. }
+            ) => {
+                let flags = flags.iter()
+                    .filter(|flag| other_flags.contains(flag))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let mask = flags.iter().fold(0u32, |mask, flag| mask | as_u32(*flag));
+                // Masked default may not be a known value, e.g. generated enums
+                Some(SpaChoice::Flags {
+                    default: T::try_from(as_u32(*default) & mask).ok()?,
+                    flags,
+                })
+            }
+            (SpaChoice::Flags { .. }, _) | (_, SpaChoice::Flags { .. }) => None,
+            _ => {
+                let (self_minimum, self_maximum, self_step) = self.bounds();
+                let (other_minimum, other_maximum, other_step) = other.bounds();
+                let minimum = if self_minimum > other_minimum { self_minimum } else { other_minimum };
+                let maximum = if self_maximum < other_maximum { self_maximum } else { other_maximum };
+                if minimum > maximum {
+                    return None;
+                }
+                if self_step == 1 && other_step == 1 {
+                    return Some(SpaChoice::Range {
+                        default: clamp(self.default(), minimum, maximum),
+                        minimum,
+                        maximum,
+                    });
+                }
+                let step = match (self_step / greatest_common_divisor(self_step, other_step)).checked_mul(other_step) {
+                    Some(value) if value <= as_u32(maximum) - as_u32(minimum) => value,
+                    // At most one common value, looked up among values of the choice with the
+                    // largest step
+                    _ => {
+                        let (largest_minimum, largest_step) = match self_step > other_step {
+                            true => (as_u32(self_minimum) as u64, self_step as u64),
+                            false => (as_u32(other_minimum) as u64, other_step as u64),
+                        };
+                        let first = largest_minimum
+                            + (as_u32(minimum) as u64 - largest_minimum).div_ceil(largest_step) * largest_step;
+                        let value = (first..=as_u32(maximum) as u64)
+                            .step_by(largest_step as usize)
+                            .map(|value| value as u32)
+                            .find(|value| {
+                                T::try_from(*value)
+                                    .is_ok_and(|value| self.contains(value) && other.contains(value))
+                            })?;
+                        let value = T::try_from(value).ok()?;
+                        return Some(SpaChoice::Enum {
+                            default: value,
+                            alternatives: vec![value],
+                        });
+                    }
+                };
+                // First common value is found within one step period
+                let minimum = (as_u32(minimum)..=as_u32(maximum))
+                    .take(step as usize)
+                    .find(|value| {
+                        T::try_from(*value)
+                            .is_ok_and(|value| self.contains(value) && other.contains(value))
+                    })?;
+                let maximum = minimum + (as_u32(maximum) - minimum) / step * step;
+                let default = clamp(as_u32(self.default()), minimum, maximum);
+                let default = minimum + (default - minimum) / step * step;
+                Some(SpaChoice::Step {
+                    default: T::try_from(default).ok()?,
+                    minimum: T::try_from(minimum).ok()?,
+                    maximum: T::try_from(maximum).ok()?,
+                    step: T::try_from(step).ok()?,
+                })
+            }
+        }
+    }
+
+    /// Pick a single value, i.e. default one when allowed
+    pub fn fixate(&self) -> T {
+        match self {
+            SpaChoice::None(value) => *value,
+            SpaChoice::Range { default, minimum, maximum } => clamp(*default, *minimum, *maximum),
+            SpaChoice::Step { default, minimum, maximum, step } => {
+                let value = clamp(*default, *minimum, *maximum);
+                let step = as_u32(*step).max(1);
+                let aligned = as_u32(*minimum) + (as_u32(value) - as_u32(*minimum)) / step * step;
+                // Aligned value may not be a known one (e.g. generated enums), clamped one is
+                T::try_from(aligned).unwrap_or(value)
+            }
+            SpaChoice::Enum { default, .. } => *default,
+            SpaChoice::Flags { default, .. } => *default,
+        }
+    }
+}
+
+impl <T> SpaChoice<T> {
+    pub(crate) fn to_choice<V, F>(&self, map: F) -> Choice<V>
+    where
+        F: Fn(&T) -> V,
+    {
+        let choice = match self {
+            SpaChoice::None(value) => ChoiceEnum::None(map(value)),
+            SpaChoice::Range { default, minimum, maximum } => ChoiceEnum::Range {
+                default: map(default),
+                min: map(minimum),
+                max: map(maximum),
+            },
+            SpaChoice::Step { default, minimum, maximum, step } => ChoiceEnum::Step {
+                default: map(default),
+                min: map(minimum),
+                max: map(maximum),
+                step: map(step),
+            },
+            SpaChoice::Enum { default, alternatives } => ChoiceEnum::Enum {
+                default: map(default),
+                alternatives: alternatives.iter().map(&map).collect(),
+            },
+            SpaChoice::Flags { default, flags } => ChoiceEnum::Flags {
+                default: map(default),
+                flags: flags.iter().map(&map).collect(),
+            },
+        };
+        Choice(ChoiceFlags::empty(), choice)
+    }
+
+    /// Fails as soon as a single value fails to map
+    pub(crate) fn try_map<U, E, F>(self, map: F) -> Result<SpaChoice<U>, E>
+    where
+        F: Fn(T) -> Result<U, E>,
+    {
+        let map_values = |values: Vec<T>| values.into_iter().map(&map).collect::<Result<Vec<_>, _>>();
+        Ok(match self {
+            SpaChoice::None(value) => SpaChoice::None(map(value)?),
+            SpaChoice::Range { default, minimum, maximum } => SpaChoice::Range {
+                default: map(default)?,
+                minimum: map(minimum)?,
+                maximum: map(maximum)?,
+            },
+            SpaChoice::Step { default, minimum, maximum, step } => SpaChoice::Step {
+                default: map(default)?,
+                minimum: map(minimum)?,
+                maximum: map(maximum)?,
+                step: map(step)?,
+            },
+            SpaChoice::Enum { default, alternatives } => SpaChoice::Enum {
+                default: map(default)?,
+                alternatives: map_values(alternatives)?,
+            },
+            SpaChoice::Flags { default, flags } => SpaChoice::Flags {
+                default: map(default)?,
+                flags: map_values(flags)?,
+            },
+        })
+    }
+
+    pub(crate) fn from_choice<V, F>(choice: ChoiceEnum<V>, map: F) -> Self
+    where
+        F: Fn(V) -> T,
+    {
+        match choice {
+            ChoiceEnum::None(value) => SpaChoice::None(map(value)),
+            ChoiceEnum::Range { default, min, max } => SpaChoice::Range {
+                default: map(default),
+                minimum: map(min),
+                maximum: map(max),
+            },
+            ChoiceEnum::Step { default, min, max, step } => SpaChoice::Step {
+                default: map(default),
+                minimum: map(min),
+                maximum: map(max),
+                step: map(step),
+            },
+            ChoiceEnum::Enum { default, alternatives } => SpaChoice::Enum {
+                default: map(default),
+                alternatives: alternatives.into_iter().map(&map).collect(),
+            },
+            ChoiceEnum::Flags { default, flags } => SpaChoice::Flags {
+                default: map(default),
+                flags: flags.into_iter().map(&map).collect(),
+            },
+        }
+    }
+}
+
+impl <T> From<T> for SpaChoice<T> {
+    fn from(value: T) -> Self {
+        SpaChoice::None(value)
+    }
+}
+
+impl <T: TryFrom<u32>> TryFrom<Value> for SpaChoice<T> {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let choice: SpaChoice<u32> = match value {
+            Value::Int(value) => SpaChoice::None(value as u32),
+            Value::Id(value) => SpaChoice::None(value.0),
+            Value::Choice(ChoiceValue::Int(value)) => SpaChoice::from_choice(value.1, |value| value as u32),
+            Value::Choice(ChoiceValue::Id(value)) => SpaChoice::from_choice(value.1, |value| value.0),
+            _ => return Err(DeserializationError::invalid_type("Int, Id, Choice<Int> or Choice<Id>", value)),
+        };
+        choice.try_map(|value| {
+            T::try_from(value)
+                .map_err(|_| DeserializationError::invalid_type(short_type_name::<T>(), value))
+        })
+    }
+}
+
+impl <'de, T: TryFrom<u32>> PodDeserialize<'de> for SpaChoice<T> {
+    fn deserialize(deserializer: PodDeserializer<'de>) -> Result<(Self, DeserializeSuccess<'de>), DeserializeError<&'de [u8]>>
+    where
+        Self: Sized
+    {
+        let res = deserializer.deserialize_any()?;
+        Ok((res.0.try_into()?, res.1))
+    }
+}
+
+impl PodSerialize for SpaChoice<u32> {
+    fn serialize<O: Write + Seek>(&self, serializer: PodSerializer<O>) -> Result<SerializeSuccess<O>, GenError> {
+        match self {
+            SpaChoice::None(value) => serializer.serialized_fixed_sized_pod(&(*value as i32)),
+            _ => serializer.serialize_choice(&self.to_choice(|value| *value as i32)),
+        }
+    }
+}
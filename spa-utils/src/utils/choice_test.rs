@@ -0,0 +1,192 @@
+use crate::audio::AudioSampleFormat;
+use crate::utils::choice::SpaChoice;
+use crate::utils::DeserializationError;
+use libspa::pod::{ChoiceValue, Value};
+use libspa::utils::{Choice, ChoiceEnum, ChoiceFlags, Id};
+
+fn range(default: u32, minimum: u32, maximum: u32) -> SpaChoice<u32> {
+    SpaChoice::Range {
+        default,
+        minimum,
+        maximum,
+    }
+}
+
+fn step(default: u32, minimum: u32, maximum: u32, step: u32) -> SpaChoice<u32> {
+    SpaChoice::Step {
+        default,
+        minimum,
+        maximum,
+        step,
+    }
+}
+
+fn enumeration(default: u32, alternatives: Vec<u32>) -> SpaChoice<u32> {
+    SpaChoice::Enum {
+        default,
+        alternatives,
+    }
+}
+
+#[test]
+fn contains() {
+    assert!(SpaChoice::None(48000).contains(48000));
+    assert!(!SpaChoice::None(48000).contains(44100));
+    assert!(range(48000, 8000, 192000).contains(44100));
+    assert!(!range(48000, 8000, 192000).contains(384000));
+    assert!(step(2, 2, 8, 2).contains(6));
+    assert!(!step(2, 2, 8, 2).contains(5));
+    assert!(enumeration(48000, vec![44100, 48000]).contains(44100));
+    assert!(!enumeration(48000, vec![44100, 48000]).contains(96000));
+    let flags = SpaChoice::Flags {
+        default: 0x1,
+        flags: vec![0x1, 0x4],
+    };
+    assert!(flags.contains(0x5));
+    assert!(!flags.contains(0x2));
+}
+
+#[test]
+fn intersect_keeps_enum_order() {
+    let device = enumeration(96000, vec![96000, 48000, 44100]);
+    let stream = enumeration(44100, vec![44100, 48000]);
+    assert_eq!(
+        Some(enumeration(48000, vec![48000, 44100])),
+        device.intersect(&enumeration(48000, vec![44100, 48000]))
+    );
+    assert_eq!(
+        Some(enumeration(44100, vec![44100, 48000])),
+        stream.intersect(&device)
+    );
+}
+
+#[test]
+fn intersect_keeps_enum_default() {
+    let device = enumeration(48000, vec![44100, 96000]);
+    assert_eq!(
+        Some(enumeration(48000, vec![48000, 44100])),
+        device.intersect(&range(44100, 8000, 48000))
+    );
+    assert_eq!(
+        Some(SpaChoice::None(48000)),
+        device.intersect(&SpaChoice::None(48000))
+    );
+}
+
+#[test]
+fn intersect_enum_with_range() {
+    let device = enumeration(48000, vec![44100, 48000, 96000]);
+    let stream = range(44100, 8000, 48000);
+    assert_eq!(
+        Some(enumeration(44100, vec![44100, 48000])),
+        stream.intersect(&device)
+    );
+    assert_eq!(
+        Some(enumeration(48000, vec![44100, 48000])),
+        device.intersect(&stream)
+    );
+}
+
+#[test]
+fn intersect_ranges() {
+    assert_eq!(
+        Some(range(48000, 44100, 96000)),
+        range(48000, 8000, 96000).intersect(&range(44100, 44100, 192000))
+    );
+    assert_eq!(
+        Some(range(96000, 96000, 96000)),
+        range(48000, 8000, 96000).intersect(&range(192000, 96000, 192000))
+    );
+    assert_eq!(None, range(48000, 8000, 48000).intersect(&range(96000, 96000, 192000)));
+}
+
+#[test]
+fn intersect_steps() {
+    assert_eq!(
+        Some(step(6, 6, 12, 6)),
+        step(2, 2, 12, 2).intersect(&step(3, 3, 15, 3))
+    );
+    assert_eq!(
+        Some(step(4, 4, 8, 2)),
+        step(2, 2, 8, 2).intersect(&range(5, 3, 9))
+    );
+    assert_eq!(None, step(1, 1, 3, 2).intersect(&step(2, 2, 2, 2)));
+    // Least common multiple of large coprime steps overflows, a single value is common
+    assert_eq!(
+        Some(enumeration(1, vec![1])),
+        step(1, 1, u32::MAX, 65537).intersect(&step(1, 1, u32::MAX, 65539))
+    );
+    assert_eq!(
+        Some(enumeration(12, vec![12])),
+        step(0, 0, 20, 12).intersect(&step(0, 4, 20, 8))
+    );
+}
+
+#[test]
+fn intersect_none() {
+    assert_eq!(
+        Some(SpaChoice::None(48000)),
+        SpaChoice::None(48000).intersect(&range(44100, 8000, 96000))
+    );
+    assert_eq!(None, range(44100, 8000, 96000).intersect(&SpaChoice::None(192000)));
+}
+
+#[test]
+fn intersect_ids() {
+    let device = SpaChoice::Enum {
+        default: AudioSampleFormat::S16_LE,
+        alternatives: vec![AudioSampleFormat::S16_LE, AudioSampleFormat::F32_LE],
+    };
+    let stream = SpaChoice::None(AudioSampleFormat::F32_LE);
+    assert_eq!(Some(SpaChoice::None(AudioSampleFormat::F32_LE)), device.intersect(&stream));
+}
+
+#[test]
+fn fixate() {
+    assert_eq!(48000, SpaChoice::None(48000).fixate());
+    assert_eq!(8000, range(0, 8000, 192000).fixate());
+    assert_eq!(6, step(7, 2, 8, 2).fixate());
+    // Default is the first value of a SPA enum, alternatives don't have to repeat it
+    assert_eq!(96000, enumeration(96000, vec![44100, 48000]).fixate());
+    assert_eq!(48000, enumeration(48000, vec![44100, 48000]).fixate());
+}
+
+#[test]
+fn try_from_ids() {
+    let value = Value::Choice(ChoiceValue::Id(Choice(
+        ChoiceFlags::empty(),
+        ChoiceEnum::Enum {
+            default: Id(AudioSampleFormat::F32_LE.into()),
+            alternatives: vec![Id(AudioSampleFormat::F32_LE.into()), Id(AudioSampleFormat::S16_LE.into())],
+        }
+    )));
+    assert_eq!(
+        SpaChoice::Enum {
+            default: AudioSampleFormat::F32_LE,
+            alternatives: vec![AudioSampleFormat::F32_LE, AudioSampleFormat::S16_LE],
+        },
+        SpaChoice::<AudioSampleFormat>::try_from(value).unwrap()
+    );
+}
+
+#[test]
+fn try_from_unknown_id() {
+    let value = Value::Choice(ChoiceValue::Id(Choice(
+        ChoiceFlags::empty(),
+        ChoiceEnum::Enum {
+            default: Id(AudioSampleFormat::F32_LE.into()),
+            alternatives: vec![Id(AudioSampleFormat::F32_LE.into()), Id(u32::MAX)],
+        }
+    )));
+    assert_eq!(
+        DeserializationError::InvalidType {
+            expected: "AudioSampleFormat".to_string(),
+            actual: u32::MAX.to_string(),
+        },
+        SpaChoice::<AudioSampleFormat>::try_from(value).unwrap_err()
+    );
+    assert_eq!(
+        true,
+        SpaChoice::<AudioSampleFormat>::try_from(Value::Id(Id(u32::MAX))).is_err()
+    );
+}
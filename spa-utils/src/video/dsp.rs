@@ -0,0 +1,52 @@
+use crate::format::{format_properties, serialize_format, MediaSubtype, MediaType};
+use crate::impl_any_deserializer;
+use crate::utils::choice::SpaChoice;
+use crate::utils::DeserializationError;
+use crate::video::{to_modifier, VideoFormat};
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
+use std::io::{Seek, Write};
+
+/// DSP video format (i.e. one plane of floats per pixel component)
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoInfoDsp {
+    pub format: SpaChoice<VideoFormat>,
+    pub modifier: Option<i64>,
+}
+
+impl TryFrom<Value> for VideoInfoDsp {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = format_properties(value, MediaType::Video.into(), MediaSubtype::Dsp.into())?;
+        Ok(VideoInfoDsp {
+            format: properties.required(libspa::sys::SPA_FORMAT_VIDEO_format, SpaChoice::try_from)?,
+            modifier: properties.optional(libspa::sys::SPA_FORMAT_VIDEO_modifier, to_modifier)?,
+        })
+    }
+}
+
+impl_any_deserializer!(VideoInfoDsp);
+
+impl PodSerialize for VideoInfoDsp {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serialize_format(serializer, MediaType::Video.into(), MediaSubtype::Dsp.into())?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_VIDEO_format,
+            &self.format,
+            PropertyFlags::empty(),
+        )?;
+        if let Some(modifier) = self.modifier {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_FORMAT_VIDEO_modifier,
+                &modifier,
+                PropertyFlags::MANDATORY,
+            )?;
+        }
+        object_serializer.end()
+    }
+}
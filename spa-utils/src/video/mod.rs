@@ -0,0 +1,140 @@
+use libspa::pod::deserialize::DeserializeError;
+use libspa::pod::deserialize::DeserializeSuccess;
+use libspa::pod::deserialize::IdVisitor;
+use libspa::pod::deserialize::PodDeserialize;
+use libspa::pod::deserialize::PodDeserializer;
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{ChoiceValue, Value};
+use libspa::utils::{ChoiceEnum, Fraction, Id, Rectangle};
+use std::io::{Seek, Write};
+use crate::{impl_choice_id_serializer, impl_id_deserializer, impl_id_serializer};
+use crate::utils::choice::SpaChoice;
+use crate::utils::DeserializationError;
+
+pub mod dsp;
+pub mod raw;
+
+#[cfg(test)]
+#[path = "video_test.rs"]
+mod video_test;
+
+include!(concat!(env!("OUT_DIR"), "/video.rs"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl VideoSize {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+        }
+    }
+}
+
+impl From<Rectangle> for VideoSize {
+    fn from(value: Rectangle) -> Self {
+        Self::new(value.width, value.height)
+    }
+}
+
+impl From<VideoSize> for Rectangle {
+    fn from(value: VideoSize) -> Self {
+        Rectangle {
+            width: value.width,
+            height: value.height,
+        }
+    }
+}
+
+/// Frames per second as a fraction, 0/1 means variable framerate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoFraction {
+    pub numerator: u32,
+    pub denominator: u32,
+}
+
+impl VideoFraction {
+    pub fn new(numerator: u32, denominator: u32) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+}
+
+impl From<Fraction> for VideoFraction {
+    fn from(value: Fraction) -> Self {
+        Self::new(value.num, value.denom)
+    }
+}
+
+impl From<VideoFraction> for Fraction {
+    fn from(value: VideoFraction) -> Self {
+        Fraction {
+            num: value.numerator,
+            denom: value.denominator,
+        }
+    }
+}
+
+pub(crate) fn to_size_choice(value: Value) -> Result<SpaChoice<VideoSize>, DeserializationError> {
+    match value {
+        Value::Rectangle(value) => Ok(SpaChoice::None(value.into())),
+        Value::Choice(ChoiceValue::Rectangle(value)) => Ok(SpaChoice::from_choice(value.1, VideoSize::from)),
+        _ => Err(DeserializationError::invalid_type("Rectangle or Choice<Rectangle>", value)),
+    }
+}
+
+pub(crate) fn to_fraction_choice(value: Value) -> Result<SpaChoice<VideoFraction>, DeserializationError> {
+    match value {
+        Value::Fraction(value) => Ok(SpaChoice::None(value.into())),
+        Value::Choice(ChoiceValue::Fraction(value)) => Ok(SpaChoice::from_choice(value.1, VideoFraction::from)),
+        _ => Err(DeserializationError::invalid_type("Fraction or Choice<Fraction>", value)),
+    }
+}
+
+pub(crate) fn to_fraction(value: Value) -> Result<VideoFraction, DeserializationError> {
+    match value {
+        Value::Fraction(value) => Ok(value.into()),
+        _ => Err(DeserializationError::invalid_type("Fraction", value)),
+    }
+}
+
+/// DMA-BUF modifiers are usually advertised as an enum, the preferred one is kept
+pub(crate) fn to_modifier(value: Value) -> Result<i64, DeserializationError> {
+    match value {
+        Value::Long(value) => Ok(value),
+        Value::Choice(ChoiceValue::Long(value)) => match value.1 {
+            ChoiceEnum::None(value) => Ok(value),
+            ChoiceEnum::Enum { default, .. } => Ok(default),
+            _ => Err(DeserializationError::invalid_type("Choice<Long> enum", "Choice<Long>")),
+        },
+        _ => Err(DeserializationError::invalid_type("Long", value)),
+    }
+}
+
+impl PodSerialize for SpaChoice<VideoSize> {
+    fn serialize<O: Write + Seek>(&self, serializer: PodSerializer<O>) -> Result<SerializeSuccess<O>, GenError> {
+        match self {
+            SpaChoice::None(value) => serializer.serialized_fixed_sized_pod(&Rectangle::from(*value)),
+            _ => serializer.serialize_choice(&self.to_choice(|value| Rectangle::from(*value))),
+        }
+    }
+}
+
+impl PodSerialize for SpaChoice<VideoFraction> {
+    fn serialize<O: Write + Seek>(&self, serializer: PodSerializer<O>) -> Result<SerializeSuccess<O>, GenError> {
+        match self {
+            SpaChoice::None(value) => serializer.serialized_fixed_sized_pod(&Fraction::from(*value)),
+            _ => serializer.serialize_choice(&self.to_choice(|value| Fraction::from(*value))),
+        }
+    }
+}
+
+impl_id_deserializer!(VideoFormat);
+impl_id_serializer!(VideoFormat);
+impl_choice_id_serializer!(VideoFormat);
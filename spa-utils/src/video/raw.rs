@@ -0,0 +1,77 @@
+use crate::format::{format_properties, serialize_format, MediaSubtype, MediaType};
+use crate::impl_any_deserializer;
+use crate::utils::choice::SpaChoice;
+use crate::utils::DeserializationError;
+use crate::video::{to_fraction, to_fraction_choice, to_modifier, to_size_choice, VideoFormat, VideoFraction, VideoSize};
+use libspa::pod::deserialize::{DeserializeError, DeserializeSuccess, PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{GenError, PodSerialize, PodSerializer, SerializeSuccess};
+use libspa::pod::{PropertyFlags, Value};
+use libspa::utils::Fraction;
+use std::io::{Seek, Write};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoInfoRaw {
+    pub format: SpaChoice<VideoFormat>,
+    /// DMA-BUF modifier, only set when buffers are shared as DMA-BUF
+    pub modifier: Option<i64>,
+    pub size: SpaChoice<VideoSize>,
+    pub framerate: SpaChoice<VideoFraction>,
+    /// Upper bound for variable framerate (i.e. framerate of 0/1)
+    pub max_framerate: Option<VideoFraction>,
+}
+
+impl TryFrom<Value> for VideoInfoRaw {
+    type Error = DeserializationError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let mut properties = format_properties(value, MediaType::Video.into(), MediaSubtype::Raw.into())?;
+        Ok(VideoInfoRaw {
+            format: properties.required(libspa::sys::SPA_FORMAT_VIDEO_format, SpaChoice::try_from)?,
+            modifier: properties.optional(libspa::sys::SPA_FORMAT_VIDEO_modifier, to_modifier)?,
+            size: properties.required(libspa::sys::SPA_FORMAT_VIDEO_size, to_size_choice)?,
+            framerate: properties.required(libspa::sys::SPA_FORMAT_VIDEO_framerate, to_fraction_choice)?,
+            max_framerate: properties.optional(libspa::sys::SPA_FORMAT_VIDEO_maxFramerate, to_fraction)?,
+        })
+    }
+}
+
+impl_any_deserializer!(VideoInfoRaw);
+
+impl PodSerialize for VideoInfoRaw {
+    fn serialize<O: Write + Seek>(
+        &self,
+        serializer: PodSerializer<O>
+    ) -> Result<SerializeSuccess<O>, GenError> {
+        let mut object_serializer = serialize_format(serializer, MediaType::Video.into(), MediaSubtype::Raw.into())?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_VIDEO_format,
+            &self.format,
+            PropertyFlags::empty(),
+        )?;
+        if let Some(modifier) = self.modifier {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_FORMAT_VIDEO_modifier,
+                &modifier,
+                PropertyFlags::MANDATORY,
+            )?;
+        }
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_VIDEO_size,
+            &self.size,
+            PropertyFlags::empty(),
+        )?;
+        object_serializer.serialize_property(
+            libspa::sys::SPA_FORMAT_VIDEO_framerate,
+            &self.framerate,
+            PropertyFlags::empty(),
+        )?;
+        if let Some(max_framerate) = self.max_framerate {
+            object_serializer.serialize_property(
+                libspa::sys::SPA_FORMAT_VIDEO_maxFramerate,
+                &Fraction::from(max_framerate),
+                PropertyFlags::empty(),
+            )?;
+        }
+        object_serializer.end()
+    }
+}
@@ -0,0 +1,66 @@
+use crate::utils::choice::SpaChoice;
+use crate::video::dsp::VideoInfoDsp;
+use crate::video::raw::VideoInfoRaw;
+use crate::video::{VideoFormat, VideoFraction, VideoSize};
+use libspa::pod::deserialize::{PodDeserialize, PodDeserializer};
+use libspa::pod::serialize::{PodSerialize, PodSerializer};
+use std::io::Cursor;
+
+fn round_trip<P>(value: &P) -> P
+where
+    P: PodSerialize + for<'de> PodDeserialize<'de>
+{
+    let bytes = PodSerializer::serialize(Cursor::new(Vec::new()), value)
+        .unwrap()
+        .0
+        .into_inner();
+    let (_, value) = PodDeserializer::deserialize_from::<P>(&bytes).unwrap();
+    value
+}
+
+#[test]
+fn video_format() {
+    assert_eq!(VideoFormat::RGBA, VideoFormat::try_from(libspa::sys::SPA_VIDEO_FORMAT_RGBA).unwrap());
+    assert_eq!(libspa::sys::SPA_VIDEO_FORMAT_DSP_F32, u32::from(VideoFormat::DSP_F32));
+    assert_eq!(Some(VideoFormat::YUY2), VideoFormat::from_name("YUY2"));
+}
+
+#[test]
+fn raw() {
+    let format = VideoInfoRaw {
+        format: SpaChoice::Enum {
+            default: VideoFormat::BGRx,
+            alternatives: vec![VideoFormat::BGRx, VideoFormat::RGBx, VideoFormat::YUY2],
+        },
+        modifier: None,
+        size: SpaChoice::Range {
+            default: VideoSize::new(1920, 1080),
+            minimum: VideoSize::new(1, 1),
+            maximum: VideoSize::new(4096, 4096),
+        },
+        framerate: SpaChoice::None(VideoFraction::new(0, 1)),
+        max_framerate: Some(VideoFraction::new(60, 1)),
+    };
+    assert_eq!(format, round_trip(&format));
+}
+
+#[test]
+fn raw_with_modifier() {
+    let format = VideoInfoRaw {
+        format: SpaChoice::None(VideoFormat::BGRA),
+        modifier: Some(0),
+        size: SpaChoice::None(VideoSize::new(640, 480)),
+        framerate: SpaChoice::None(VideoFraction::new(30, 1)),
+        max_framerate: None,
+    };
+    assert_eq!(format, round_trip(&format));
+}
+
+#[test]
+fn dsp() {
+    let format = VideoInfoDsp {
+        format: SpaChoice::None(VideoFormat::DSP_F32),
+        modifier: None,
+    };
+    assert_eq!(format, round_trip(&format));
+}
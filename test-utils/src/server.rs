@@ -600,6 +600,10 @@ impl ContainerizedServer {
         self.api.restart();
     }
 
+    pub fn socket_path(&self) -> PathBuf {
+        self.api.socket_location().join(self.api.socket_name())
+    }
+
     pub fn set_socket_env_vars(&self) {
         std::env::set_var(PIPEWIRE_RUNTIME_DIR_ENVIRONMENT_KEY, self.api.socket_location());
         std::env::set_var(PIPEWIRE_REMOTE_ENVIRONMENT_KEY, self.api.socket_name());
@@ -644,6 +648,14 @@ impl Server {
         }
     }
 
+    /// Local server socket is resolved from environment
+    pub fn socket_path(&self) -> Option<PathBuf> {
+        match self {
+            Server::Containerized(value) => Some(value.socket_path()),
+            Server::Local => None,
+        }
+    }
+
     pub fn clone(&self) -> Self {
         match self {
             Server::Containerized(value) => Server::Containerized(value.clone()),